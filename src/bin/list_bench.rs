@@ -0,0 +1,271 @@
+//! Benchmark of the two doubly linked lists.
+//!
+//! Runs the same operations on `DoublyLinkedList`, whose nodes are
+//! `Rc<RefCell<_>>` linked by `Rc` and `Weak`, and on `RawDoublyLinkedList`,
+//! whose nodes are boxes linked by `NonNull`, and prints the median time of
+//! each operation and how much faster the raw list is.
+//!
+//! ```text
+//! cargo run --release --bin list_bench -- --len 1000000 --runs 11
+//! ```
+
+use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+use rs_algorithm_practise::data_structure::raw_doubly_linked_list::RawDoublyLinkedList;
+use rs_algorithm_practise::supplementary::random::RandomGen;
+use std::hint::black_box;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: list_bench [OPTIONS]
+
+Options:
+  --len <N>                elements in the list (default: 100000)
+  --runs <N>               timed runs per list (default: 5)
+  --seed <N>               seed of the generated elements (default: 42)
+  -h, --help               print this message";
+
+/// settings of a benchmark session, parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    len: usize,
+    runs: usize,
+    seed: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            len: 100_000,
+            runs: 5,
+            seed: 42,
+        }
+    }
+}
+
+impl Config {
+    /// parse the command line arguments (without the program name).
+    /// returns `Ok(None)` if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Config>, String> {
+        let mut config = Config::default();
+        while let Some(flag) = args.next() {
+            if flag == "-h" || flag == "--help" {
+                return Ok(None);
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for `{}`", flag))?;
+            let value = parse_number(&value)?;
+            match flag.as_str() {
+                "--len" => config.len = value,
+                "--runs" => config.runs = value,
+                "--seed" => config.seed = value,
+                _ => return Err(format!("unknown option `{}`", flag)),
+            }
+        }
+        if config.runs == 0 {
+            return Err("`--runs` must be at least 1".to_string());
+        }
+        Ok(Some(config))
+    }
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.parse().map_err(|_| format!("invalid number `{}`", s))
+}
+
+/// the operations timed on every list, in the order they run
+const OPERATIONS: [&str; 6] = [
+    "push_back",
+    "iter",
+    "iter().rev()",
+    "cursor insert",
+    "sort",
+    "pop_front",
+];
+
+/// the operations of a list, so both lists run the same code
+trait List: Default {
+    const NAME: &'static str;
+
+    fn push_back(&mut self, x: u64);
+    fn pop_front(&mut self) -> Option<u64>;
+    fn sum(&self) -> u64;
+    fn sum_rev(&self) -> u64;
+    /// insert a copy after every element, walking the list with a cursor
+    fn insert_after_each(&mut self);
+    fn sort(&mut self);
+}
+
+macro_rules! impl_list {
+    ($list:ident) => {
+        impl List for $list<u64> {
+            const NAME: &'static str = stringify!($list);
+
+            fn push_back(&mut self, x: u64) {
+                $list::push_back(self, x)
+            }
+
+            fn pop_front(&mut self) -> Option<u64> {
+                $list::pop_front(self)
+            }
+
+            fn sum(&self) -> u64 {
+                self.iter().fold(0, |sum, &x| sum.wrapping_add(x))
+            }
+
+            fn sum_rev(&self) -> u64 {
+                self.iter().rev().fold(0, |sum, &x| sum.wrapping_add(x))
+            }
+
+            fn insert_after_each(&mut self) {
+                let mut cursor = self.cursor_front_mut();
+                while let Some(&mut x) = cursor.current() {
+                    cursor.insert_after(x);
+                    cursor.move_next();
+                    cursor.move_next();
+                }
+            }
+
+            fn sort(&mut self) {
+                $list::sort(self)
+            }
+        }
+    };
+}
+
+impl_list!(DoublyLinkedList);
+impl_list!(RawDoublyLinkedList);
+
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+/// run every operation once on a new list of `L`, and return their times
+fn run_once<L: List>(elements: &[u64]) -> [Duration; OPERATIONS.len()] {
+    let mut list = L::default();
+    let push_back = time(|| {
+        for &x in elements {
+            list.push_back(x);
+        }
+    });
+    let iter = time(|| {
+        black_box(list.sum());
+    });
+    let rev = time(|| {
+        black_box(list.sum_rev());
+    });
+    let cursor = time(|| list.insert_after_each());
+    let sort = time(|| list.sort());
+    let pop_front = time(|| {
+        while let Some(x) = list.pop_front() {
+            black_box(x);
+        }
+    });
+    [push_back, iter, rev, cursor, sort, pop_front]
+}
+
+/// the median time of every operation on `L`
+fn measure<L: List>(config: &Config, elements: &[u64]) -> [Duration; OPERATIONS.len()] {
+    let samples: Vec<_> = (0..config.runs).map(|_| run_once::<L>(elements)).collect();
+    std::array::from_fn(|i| {
+        let mut times: Vec<Duration> = samples.iter().map(|sample| sample[i]).collect();
+        times.sort();
+        times[times.len() / 2]
+    })
+}
+
+fn bench(config: &Config) {
+    let mut rng = RandomGen::new(config.seed);
+    let elements: Vec<u64> = (0..config.len)
+        .map(|_| rng.rand_usize(usize::MAX) as u64)
+        .collect();
+    println!("{} elements, {} runs\n", config.len, config.runs);
+
+    let rc = measure::<DoublyLinkedList<u64>>(config, &elements);
+    let raw = measure::<RawDoublyLinkedList<u64>>(config, &elements);
+    println!(
+        "{:<14} {:>18} {:>20} {:>9}",
+        "operation",
+        <DoublyLinkedList<u64> as List>::NAME,
+        <RawDoublyLinkedList<u64> as List>::NAME,
+        "speedup"
+    );
+    for (i, operation) in OPERATIONS.iter().enumerate() {
+        let speedup = rc[i].as_secs_f64() / raw[i].as_secs_f64().max(f64::EPSILON);
+        println!(
+            "{:<14} {:>18} {:>20} {:>8.2}x",
+            operation,
+            format!("{:.3?}", rc[i]),
+            format!("{:.3?}", raw[i]),
+            speedup
+        );
+    }
+}
+
+fn main() {
+    let config = match Config::parse(std::env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    };
+    bench(&config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Config::parse(args("")), Ok(Some(Config::default())));
+        assert_eq!(Config::parse(args("--len 5 --help")), Ok(None));
+        assert_eq!(
+            Config::parse(args("--len 100 --runs 3")),
+            Ok(Some(Config {
+                len: 100,
+                runs: 3,
+                ..Config::default()
+            }))
+        );
+        assert!(Config::parse(args("--runs 0")).is_err());
+        assert!(Config::parse(args("--len many")).is_err());
+        assert!(Config::parse(args("--depth 3")).is_err());
+    }
+
+    fn check_operations<L: List>() {
+        let mut list = L::default();
+        for x in [3, 1, 2] {
+            list.push_back(x);
+        }
+        assert_eq!((list.sum(), list.sum_rev()), (6, 6));
+        list.insert_after_each();
+        list.sort();
+        let elements: Vec<u64> = std::iter::from_fn(|| list.pop_front()).collect();
+        assert_eq!(elements, vec![1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_both_lists_agree() {
+        check_operations::<DoublyLinkedList<u64>>();
+        check_operations::<RawDoublyLinkedList<u64>>();
+        let config = Config {
+            len: 1000,
+            runs: 1,
+            seed: 1,
+        };
+        bench(&config);
+    }
+}
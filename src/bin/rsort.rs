@@ -0,0 +1,583 @@
+//! A command-line sort tool built on the sorting algorithms of this crate.
+//!
+//! Reads lines (or whitespace-separated numbers with `--numeric`) from files or
+//! stdin, sorts them with the selected algorithm and prints them.
+//!
+//! ```text
+//! cat data.csv | cargo run --release --bin rsort -- -t , -k 2 -n -r --stats
+//! ```
+
+use rs_algorithm_practise::data_structure::heap::heap_sort;
+use rs_algorithm_practise::sorting_algorithms::bubble_sort::{better_bubble_sort, bubble_sort};
+use rs_algorithm_practise::sorting_algorithms::insertion_sort::insertion_sort;
+use rs_algorithm_practise::sorting_algorithms::merge_sort::merge_sort;
+use rs_algorithm_practise::sorting_algorithms::parallel::{ParallelConfig, SplitTree};
+use rs_algorithm_practise::sorting_algorithms::quick_sort::{quick_sort, rayon_quick_sort_traced};
+use rs_algorithm_practise::sorting_algorithms::selection_sort::selection_sort;
+use std::cmp::Ordering;
+use std::io::{BufWriter, Read, Write};
+use std::process::exit;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// stack size of the sorting threads. `quick_sort` picks the first element as
+/// its pivot, so it recurses once per element on sorted inputs.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+const USAGE: &str = "\
+Usage: rsort [OPTIONS] [FILE...]
+
+Sorts the lines of the given files (or stdin if none, or `-`) and prints them.
+
+Options:
+  -a, --algorithm <NAME>   sorting algorithm (default: merge_sort)
+                           bubble_sort, better_bubble_sort, selection_sort,
+                           insertion_sort, merge_sort, quick_sort, heap_sort,
+                           rayon_quick_sort (same as `-p`)
+  -p, --parallel           sort with rayon_quick_sort
+      --threads <N>        threads of the parallel sort (default: all cores)
+      --cutoff <N>         parts of at most N items are sorted sequentially
+      --max-depth <N>      parts at N nested splits or deeper are sorted
+                           sequentially
+  -n, --numeric            compare numerically; without `--key` every
+                           whitespace-separated number is an item
+  -r, --reverse            sort in descending order
+  -u, --unique             print only the first of the items with equal keys
+  -k, --key <N>            compare the N-th field (starting at 1) of each line
+  -t, --separator <SEP>    field separator for `--key` (default: whitespace)
+  -s, --stats              print the number of comparisons and the time to stderr,
+                           and the split tree of the parallel sort
+  -h, --help               print this message";
+
+/// an algorithm of this crate that can sort the items
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Algorithm {
+    Bubble,
+    BetterBubble,
+    Selection,
+    Insertion,
+    Merge,
+    Quick,
+    RayonQuick,
+    Heap,
+}
+
+impl Algorithm {
+    fn from_name(name: &str) -> Option<Algorithm> {
+        match name {
+            "bubble_sort" => Some(Algorithm::Bubble),
+            "better_bubble_sort" => Some(Algorithm::BetterBubble),
+            "selection_sort" => Some(Algorithm::Selection),
+            "insertion_sort" => Some(Algorithm::Insertion),
+            "merge_sort" => Some(Algorithm::Merge),
+            "quick_sort" => Some(Algorithm::Quick),
+            "rayon_quick_sort" => Some(Algorithm::RayonQuick),
+            "heap_sort" => Some(Algorithm::Heap),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Bubble => "bubble_sort",
+            Algorithm::BetterBubble => "better_bubble_sort",
+            Algorithm::Selection => "selection_sort",
+            Algorithm::Insertion => "insertion_sort",
+            Algorithm::Merge => "merge_sort",
+            Algorithm::Quick => "quick_sort",
+            Algorithm::RayonQuick => "rayon_quick_sort",
+            Algorithm::Heap => "heap_sort",
+        }
+    }
+
+    /// sort `items` in place, and return the split tree of the parallel sort
+    fn sort<T: Ord + Copy + Send>(
+        &self,
+        items: &mut Vec<T>,
+        config: &ParallelConfig,
+    ) -> Option<SplitTree> {
+        if *self == Algorithm::RayonQuick {
+            return Some(rayon_quick_sort_traced(items, config));
+        }
+        match self {
+            Algorithm::Bubble => bubble_sort(items),
+            Algorithm::BetterBubble => better_bubble_sort(items),
+            Algorithm::Selection => selection_sort(items),
+            Algorithm::Insertion => insertion_sort(items),
+            Algorithm::Merge => *items = merge_sort(std::mem::take(items)),
+            Algorithm::Quick => quick_sort(items),
+            Algorithm::Heap => heap_sort(items),
+            Algorithm::RayonQuick => unreachable!(),
+        }
+        None
+    }
+}
+
+/// settings of the tool, parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    algorithm: Algorithm,
+    numeric: bool,
+    reverse: bool,
+    unique: bool,
+    key: Option<usize>,
+    separator: Option<String>,
+    stats: bool,
+    threads: Option<usize>,
+    cutoff: Option<usize>,
+    max_depth: Option<usize>,
+    files: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            algorithm: Algorithm::Merge,
+            numeric: false,
+            reverse: false,
+            unique: false,
+            key: None,
+            separator: None,
+            stats: false,
+            threads: None,
+            cutoff: None,
+            max_depth: None,
+            files: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// the configuration of the parallel sort, on a pool with enough stack
+    fn parallel_config(&self) -> Result<ParallelConfig, String> {
+        let mut builder = rayon::ThreadPoolBuilder::new().stack_size(STACK_SIZE);
+        if let Some(threads) = self.threads {
+            builder = builder.num_threads(threads);
+        }
+        let pool = builder.build().map_err(|e| e.to_string())?;
+        let default = ParallelConfig::default();
+        Ok(ParallelConfig {
+            sequential_cutoff: self.cutoff.unwrap_or(default.sequential_cutoff),
+            max_depth: self.max_depth.unwrap_or(default.max_depth),
+            pool: Some(Arc::new(pool)),
+        })
+    }
+
+    /// parse the command line arguments (without the program name).
+    /// returns `Ok(None)` if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+        let mut options = Options::default();
+        let mut algorithm = None;
+        let mut parallel = false;
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{}`", flag))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-a" | "--algorithm" => {
+                    let name = value(&arg)?;
+                    algorithm = Some(
+                        Algorithm::from_name(&name)
+                            .ok_or_else(|| format!("unknown algorithm `{}`", name))?,
+                    );
+                }
+                "-p" | "--parallel" => parallel = true,
+                "-n" | "--numeric" => options.numeric = true,
+                "-r" | "--reverse" => options.reverse = true,
+                "-u" | "--unique" => options.unique = true,
+                "-k" | "--key" => {
+                    let n = value(&arg)?;
+                    match n.parse() {
+                        Ok(n) if n > 0 => options.key = Some(n),
+                        _ => return Err(format!("invalid key column `{}`", n)),
+                    }
+                }
+                "-t" | "--separator" => {
+                    let sep = value(&arg)?;
+                    if sep.is_empty() {
+                        return Err("the separator must not be empty".to_string());
+                    }
+                    options.separator = Some(sep);
+                }
+                "-s" | "--stats" => options.stats = true,
+                "--threads" => {
+                    let n = value(&arg)?;
+                    match n.parse() {
+                        Ok(n) if n > 0 => options.threads = Some(n),
+                        _ => return Err(format!("invalid number of threads `{}`", n)),
+                    }
+                }
+                "--cutoff" | "--max-depth" => {
+                    let n = value(&arg)?;
+                    let n = n.parse().map_err(|_| format!("invalid number `{}`", n))?;
+                    match arg.as_str() {
+                        "--cutoff" => options.cutoff = Some(n),
+                        _ => options.max_depth = Some(n),
+                    }
+                }
+                "-" => options.files.push(arg),
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+                _ => options.files.push(arg),
+            }
+        }
+        // the tuning flags only make sense for the parallel sort
+        parallel |=
+            options.threads.is_some() || options.cutoff.is_some() || options.max_depth.is_some();
+        options.algorithm = match (algorithm, parallel) {
+            (Some(a), true) if a != Algorithm::RayonQuick => {
+                return Err(format!("`{}` cannot run in parallel", a.name()))
+            }
+            (_, true) => Algorithm::RayonQuick,
+            (Some(a), false) => a,
+            (None, false) => Algorithm::Merge,
+        };
+        Ok(Some(options))
+    }
+}
+
+/// the part of an item that is compared
+#[derive(Debug, Clone, PartialEq)]
+enum Key {
+    Text(String),
+    Number(f64),
+    /// the field of a numeric `--key` is missing, it sorts before every number
+    Missing,
+}
+
+impl Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        match (self, other) {
+            (Key::Text(a), Key::Text(b)) => a.cmp(b),
+            (Key::Number(a), Key::Number(b)) => a.total_cmp(b),
+            (Key::Missing, Key::Missing) => Ordering::Equal,
+            (Key::Missing, _) => Ordering::Less,
+            (_, Key::Missing) => Ordering::Greater,
+            // all keys of a run have the same kind
+            (Key::Text(_), Key::Number(_)) => Ordering::Greater,
+            (Key::Number(_), Key::Text(_)) => Ordering::Less,
+        }
+    }
+}
+
+/// an item to be sorted, i.e. a line or a number
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    text: String,
+    key: Key,
+}
+
+/// split the input into items according to the options.
+///
+/// # Errors
+/// return a message with the line number if a numeric key cannot be parsed
+fn parse_items(input: &str, options: &Options) -> Result<Vec<Item>, String> {
+    let number = |s: &str, line: usize| {
+        s.parse::<f64>()
+            .map(Key::Number)
+            .map_err(|_| format!("line {}: `{}` is not a number", line, s))
+    };
+
+    let mut items = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match options.key {
+            // every whitespace-separated number is an item
+            None if options.numeric => {
+                for token in line.split_whitespace() {
+                    items.push(Item {
+                        text: token.to_string(),
+                        key: number(token, i + 1)?,
+                    });
+                }
+            }
+            None => items.push(Item {
+                text: line.to_string(),
+                key: Key::Text(line.to_string()),
+            }),
+            Some(column) => {
+                let field = match &options.separator {
+                    Some(sep) => line.split(sep.as_str()).nth(column - 1),
+                    None => line.split_whitespace().nth(column - 1),
+                };
+                // lines with too few fields get an empty key, or the
+                // smallest key if it is numeric
+                let key = match (field, options.numeric) {
+                    (Some(field), true) => number(field.trim(), i + 1)?,
+                    (None, true) => Key::Missing,
+                    (field, false) => Key::Text(field.unwrap_or("").to_string()),
+                };
+                items.push(Item {
+                    text: line.to_string(),
+                    key,
+                });
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// a reference to an item, which is what the algorithms actually sort.
+/// it is `Copy` (which `insertion_sort` requires) and counts its comparisons.
+#[derive(Debug, Clone, Copy)]
+struct Entry<'a> {
+    index: usize,
+    item: &'a Item,
+    reverse: bool,
+    comparisons: &'a AtomicUsize,
+}
+
+impl Entry<'_> {
+    fn cmp_key(&self, other: &Self) -> Ordering {
+        self.comparisons.fetch_add(1, atomic::Ordering::Relaxed);
+        let ord = self.item.key.cmp(&other.item.key);
+        match self.reverse {
+            true => ord.reverse(),
+            false => ord,
+        }
+    }
+}
+
+impl PartialEq for Entry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry<'_> {}
+
+impl PartialOrd for Entry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry<'_> {
+    /// equal keys keep their input order, so every algorithm gives the same output
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_key(other).then(self.index.cmp(&other.index))
+    }
+}
+
+/// the result of sorting the items
+struct Sorted<'a> {
+    texts: Vec<&'a str>,
+    comparisons: usize,
+    split_tree: Option<SplitTree>,
+}
+
+/// sort the items and return the texts to print
+fn sort_items<'a>(items: &'a [Item], options: &Options, config: &ParallelConfig) -> Sorted<'a> {
+    let comparisons = AtomicUsize::new(0);
+    let mut entries: Vec<Entry> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| Entry {
+            index,
+            item,
+            reverse: options.reverse,
+            comparisons: &comparisons,
+        })
+        .collect();
+
+    let split_tree = options.algorithm.sort(&mut entries, config);
+    if options.unique {
+        entries.dedup_by(|a, b| a.cmp_key(b) == Ordering::Equal);
+    }
+    let texts = entries
+        .iter()
+        .map(|e| items[e.index].text.as_str())
+        .collect();
+    Sorted {
+        texts,
+        comparisons: comparisons.into_inner(),
+        split_tree,
+    }
+}
+
+fn read_input(files: &[String]) -> Result<String, String> {
+    let mut input = String::new();
+    if files.is_empty() {
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("cannot read stdin: {}", e))?;
+        return Ok(input);
+    }
+    for file in files {
+        let content = match file.as_str() {
+            "-" => {
+                let mut s = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut s)
+                    .map_err(|e| format!("cannot read stdin: {}", e))?;
+                s
+            }
+            _ => std::fs::read_to_string(file)
+                .map_err(|e| format!("cannot read `{}`: {}", file, e))?,
+        };
+        input.push_str(&content);
+        if !input.is_empty() && !input.ends_with('\n') {
+            input.push('\n');
+        }
+    }
+    Ok(input)
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let input = read_input(&options.files)?;
+    let items = parse_items(&input, options)?;
+
+    let config = match options.algorithm {
+        Algorithm::RayonQuick => options.parallel_config()?,
+        _ => ParallelConfig::default(),
+    };
+
+    let start = Instant::now();
+    let sorted = std::thread::scope(|s| {
+        std::thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(s, || sort_items(&items, options, &config))
+            .map_err(|e| e.to_string())?
+            .join()
+            .map_err(|_| "sorting thread panicked".to_string())
+    })?;
+    let elapsed = start.elapsed();
+
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    for text in &sorted.texts {
+        writeln!(out, "{}", text).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())?;
+
+    if options.stats {
+        eprintln!(
+            "algorithm: {}\nitems: {}\noutput: {}\ncomparisons: {}\ntime: {:.3} ms",
+            options.algorithm.name(),
+            items.len(),
+            sorted.texts.len(),
+            sorted.comparisons,
+            elapsed.as_secs_f64() * 1e3
+        );
+        if let Some(tree) = &sorted.split_tree {
+            eprint!(
+                "sequential tasks: {}\nsplit depth: {}\nlargest task: {}\nsplit tree:\n{}",
+                tree.tasks(),
+                tree.depth(),
+                tree.largest_task(),
+                tree
+            );
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    };
+    if let Err(e) = run(&options) {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(s: &str) -> Options {
+        Options::parse(s.split_whitespace().map(String::from))
+            .unwrap()
+            .unwrap()
+    }
+
+    fn sort(input: &str, args: &str) -> Vec<String> {
+        let options = options(args);
+        let items = parse_items(input, &options).unwrap();
+        let config = options.parallel_config().unwrap();
+        let sorted = sort_items(&items, &options, &config);
+        sorted.texts.into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_lexicographic() {
+        assert_eq!(sort("b\nc\na\n", ""), vec!["a", "b", "c"]);
+        assert_eq!(sort("b\nc\na\n", "-r"), vec!["c", "b", "a"]);
+        assert_eq!(sort("10\n9\n", ""), vec!["10", "9"]);
+    }
+
+    #[test]
+    fn test_numeric() {
+        assert_eq!(sort("10 9\n-1.5\n", "-n"), vec!["-1.5", "9", "10"]);
+        assert_eq!(sort("3 1 3 2 1", "-n -u -r"), vec!["3", "2", "1"]);
+        let options = options("-n");
+        assert_eq!(
+            parse_items("1\nx\n", &options),
+            Err("line 2: `x` is not a number".to_string())
+        );
+    }
+
+    #[test]
+    fn test_key_column() {
+        let input = "bob,30\nalice,25\ncarol,30\n";
+        assert_eq!(
+            sort(input, "-t , -k 2 -n"),
+            vec!["alice,25", "bob,30", "carol,30"]
+        );
+        // equal keys keep their input order, even in reverse
+        assert_eq!(
+            sort(input, "-t , -k 2 -n -r"),
+            vec!["bob,30", "carol,30", "alice,25"]
+        );
+        assert_eq!(sort(input, "-t , -k 2 -u"), vec!["alice,25", "bob,30"]);
+
+        // lines with too few fields sort first, with or without `-n`
+        let input = "b 2\na\nc -7\n";
+        assert_eq!(sort(input, "-k 2"), vec!["a", "c -7", "b 2"]);
+        assert_eq!(sort(input, "-k 2 -n"), vec!["a", "c -7", "b 2"]);
+        assert_eq!(sort(input, "-k 2 -n -r"), vec!["b 2", "c -7", "a"]);
+    }
+
+    #[test]
+    fn test_every_algorithm_agrees() {
+        let input = "5 3 9 1 3 7 2 8 0 6 4";
+        let expected = sort(input, "-n");
+        for name in [
+            "bubble_sort",
+            "better_bubble_sort",
+            "selection_sort",
+            "insertion_sort",
+            "quick_sort",
+            "heap_sort",
+            "rayon_quick_sort",
+        ] {
+            assert_eq!(sort(input, &format!("-n -a {}", name)), expected);
+        }
+        assert_eq!(sort(input, "-n -p"), expected);
+        assert_eq!(sort(input, "-n --threads 2 --cutoff 2"), expected);
+        assert!(sort("", "-a selection_sort").is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |s: &str| Options::parse(s.split_whitespace().map(String::from));
+        assert!(parse("-a shell_sort").is_err());
+        assert!(parse("-k 0").is_err());
+        assert!(parse("-p -a merge_sort").is_err());
+        assert!(parse("--frobnicate").is_err());
+        assert_eq!(parse("-h"), Ok(None));
+        assert_eq!(options("-p").algorithm, Algorithm::RayonQuick);
+        assert_eq!(options("--cutoff 8").algorithm, Algorithm::RayonQuick);
+        assert!(parse("--threads 0").is_err());
+        assert!(parse("-a heap_sort --max-depth 3").is_err());
+    }
+}
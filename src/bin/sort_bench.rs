@@ -0,0 +1,517 @@
+//! Benchmark runner for every sorting algorithm in this crate.
+//!
+//! Times each algorithm in `sorting_algorithms` (plus `heap_sort`) over a grid of
+//! input sizes and input shapes. Inputs are generated by the seedable
+//! `RandomGen`, so the same seed always produces the same inputs and results can be
+//! compared from one commit to the next.
+//!
+//! ```text
+//! cargo run --release --bin sort_bench -- --sizes 1000,10000 --runs 21 --csv bench.csv
+//! ```
+
+use rs_algorithm_practise::data_structure::heap::heap_sort;
+use rs_algorithm_practise::sorting_algorithms::bubble_sort::{better_bubble_sort, bubble_sort};
+use rs_algorithm_practise::sorting_algorithms::insertion_sort::insertion_sort;
+use rs_algorithm_practise::sorting_algorithms::merge_sort::merge_sort;
+use rs_algorithm_practise::sorting_algorithms::quick_sort::{quick_sort, rayon_quick_sort};
+use rs_algorithm_practise::sorting_algorithms::selection_sort::selection_sort;
+use rs_algorithm_practise::supplementary::random::RandomGen;
+use std::fmt::Write as _;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+/// stack size of the benchmark threads. `quick_sort` picks the first element as
+/// its pivot, so it recurses once per element on sorted inputs.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+const USAGE: &str = "\
+Usage: sort_bench [OPTIONS]
+
+Options:
+  --sizes <N,N,...>        input sizes (default: 100,1000,5000)
+  --shapes <S,S,...>       input shapes (default: all)
+                           random, sorted, reversed, sawtooth, few-unique, organ-pipe
+  --algorithms <A,A,...>   algorithms to run (default: all)
+  --runs <N>               timed runs per case (default: 11)
+  --warmup <N>             untimed runs per case (default: 1)
+  --seed <N>               seed of the input generator, below 2^32 (default: 42)
+  --max-quadratic <N>      skip O(n^2) algorithms above this size (default: 5000)
+  --csv <FILE>             also write the results as CSV (`-` for stdout)
+  --json <FILE>            also write the results as JSON (`-` for stdout)
+  -h, --help               print this message";
+
+/// the shape of a generated input
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Random,
+    Sorted,
+    Reversed,
+    Sawtooth,
+    FewUnique,
+    OrganPipe,
+}
+
+impl Shape {
+    const ALL: [Shape; 6] = [
+        Shape::Random,
+        Shape::Sorted,
+        Shape::Reversed,
+        Shape::Sawtooth,
+        Shape::FewUnique,
+        Shape::OrganPipe,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Shape::Random => "random",
+            Shape::Sorted => "sorted",
+            Shape::Reversed => "reversed",
+            Shape::Sawtooth => "sawtooth",
+            Shape::FewUnique => "few-unique",
+            Shape::OrganPipe => "organ-pipe",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Shape> {
+        Shape::ALL.into_iter().find(|shape| shape.name() == name)
+    }
+
+    /// generate an input of `n` elements of this shape.
+    /// the same generator state always produces the same input.
+    fn generate(&self, n: usize, rng: &mut RandomGen) -> Vec<u64> {
+        match self {
+            Shape::Random => (0..n).map(|_| rng.rand_usize(usize::MAX) as u64).collect(),
+            Shape::Sorted => (0..n as u64).collect(),
+            Shape::Reversed => (0..n as u64).rev().collect(),
+            // ascending runs of (roughly) sqrt(n) elements
+            Shape::Sawtooth => {
+                let tooth = ((n as f64).sqrt() as usize).max(1);
+                (0..n).map(|i| (i % tooth) as u64).collect()
+            }
+            Shape::FewUnique => (0..n).map(|_| rng.rand_usize(8) as u64).collect(),
+            // ascending first half, descending second half
+            Shape::OrganPipe => (0..n).map(|i| i.min(n - 1 - i) as u64).collect(),
+        }
+    }
+}
+
+/// a sorting algorithm under benchmark
+struct Algorithm {
+    name: &'static str,
+    quadratic: bool,
+    sort: fn(&mut Vec<u64>),
+}
+
+const ALGORITHMS: [Algorithm; 8] = [
+    Algorithm {
+        name: "bubble_sort",
+        quadratic: true,
+        sort: |v| bubble_sort(v),
+    },
+    Algorithm {
+        name: "better_bubble_sort",
+        quadratic: true,
+        sort: |v| better_bubble_sort(v),
+    },
+    Algorithm {
+        name: "selection_sort",
+        quadratic: true,
+        sort: |v| selection_sort(v),
+    },
+    Algorithm {
+        name: "insertion_sort",
+        quadratic: true,
+        sort: |v| insertion_sort(v),
+    },
+    Algorithm {
+        name: "merge_sort",
+        quadratic: false,
+        sort: |v| *v = merge_sort(std::mem::take(v)),
+    },
+    // the first element is the pivot, so sorted inputs are quadratic
+    Algorithm {
+        name: "quick_sort",
+        quadratic: true,
+        sort: |v| quick_sort(v),
+    },
+    Algorithm {
+        name: "rayon_quick_sort",
+        quadratic: true,
+        sort: |v| rayon_quick_sort(v),
+    },
+    Algorithm {
+        name: "heap_sort",
+        quadratic: false,
+        sort: heap_sort,
+    },
+];
+
+/// settings of a benchmark session, parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    sizes: Vec<usize>,
+    shapes: Vec<Shape>,
+    algorithms: Vec<&'static str>,
+    runs: usize,
+    warmup: usize,
+    seed: usize,
+    max_quadratic: usize,
+    csv: Option<String>,
+    json: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            sizes: vec![100, 1000, 5000],
+            shapes: Shape::ALL.to_vec(),
+            algorithms: ALGORITHMS.iter().map(|a| a.name).collect(),
+            runs: 11,
+            warmup: 1,
+            seed: 42,
+            max_quadratic: 5000,
+            csv: None,
+            json: None,
+        }
+    }
+}
+
+impl Config {
+    /// parse the command line arguments (without the program name).
+    /// returns `Ok(None)` if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Config>, String> {
+        let mut config = Config::default();
+        while let Some(flag) = args.next() {
+            if flag == "-h" || flag == "--help" {
+                return Ok(None);
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for `{}`", flag))?;
+            match flag.as_str() {
+                "--sizes" => config.sizes = parse_list(&value, parse_number)?,
+                "--shapes" => {
+                    config.shapes = parse_list(&value, |s| {
+                        Shape::from_name(s).ok_or_else(|| format!("unknown shape `{}`", s))
+                    })?
+                }
+                "--algorithms" => {
+                    config.algorithms = parse_list(&value, |s| {
+                        ALGORITHMS
+                            .iter()
+                            .find(|a| a.name == s)
+                            .map(|a| a.name)
+                            .ok_or_else(|| format!("unknown algorithm `{}`", s))
+                    })?
+                }
+                "--runs" => config.runs = parse_number(&value)?,
+                "--warmup" => config.warmup = parse_number(&value)?,
+                "--seed" => config.seed = parse_number(&value)?,
+                "--max-quadratic" => config.max_quadratic = parse_number(&value)?,
+                "--csv" => config.csv = Some(value),
+                "--json" => config.json = Some(value),
+                _ => return Err(format!("unknown option `{}`", flag)),
+            }
+        }
+        if config.runs == 0 {
+            return Err("`--runs` must be at least 1".to_string());
+        }
+        // `RandomGen` overflows on large states
+        if config.seed > u32::MAX as usize {
+            return Err("`--seed` must be below 2^32".to_string());
+        }
+        Ok(Some(config))
+    }
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.parse().map_err(|_| format!("invalid number `{}`", s))
+}
+
+fn parse_list<T>(s: &str, f: impl Fn(&str) -> Result<T, String>) -> Result<Vec<T>, String> {
+    s.split(',').map(|item| f(item.trim())).collect()
+}
+
+/// summary of the timed runs of one (algorithm, shape, size) case
+#[derive(Debug, Clone, PartialEq)]
+struct Stats {
+    min: Duration,
+    p25: Duration,
+    median: Duration,
+    p75: Duration,
+    p90: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    /// summarise a non-empty list of samples
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            p25: percentile(&samples, 25),
+            median: percentile(&samples, 50),
+            p75: percentile(&samples, 75),
+            p90: percentile(&samples, 90),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// nearest-rank percentile of sorted, non-empty samples
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// result of one (algorithm, shape, size) case
+struct Record {
+    algorithm: &'static str,
+    shape: Shape,
+    size: usize,
+    stats: Stats,
+}
+
+/// run every configured case and collect the results
+fn run(config: &Config) -> Result<Vec<Record>, String> {
+    let mut records = Vec::new();
+    for &size in &config.sizes {
+        for &shape in &config.shapes {
+            // every case gets its own generator, so that adding or removing
+            // cases does not change the inputs of the others
+            let mut rng = RandomGen::new(config.seed ^ size);
+            let input = shape.generate(size, &mut rng);
+            let mut expected = input.clone();
+            expected.sort();
+
+            for algorithm in ALGORITHMS.iter() {
+                if !config.algorithms.contains(&algorithm.name)
+                    || (algorithm.quadratic && size > config.max_quadratic)
+                {
+                    continue;
+                }
+                for _ in 0..config.warmup {
+                    (algorithm.sort)(&mut input.clone());
+                }
+                let mut samples = Vec::with_capacity(config.runs);
+                for _ in 0..config.runs {
+                    let mut v = input.clone();
+                    let start = Instant::now();
+                    (algorithm.sort)(&mut v);
+                    samples.push(start.elapsed());
+                    if v != expected {
+                        return Err(format!(
+                            "{} produced an unsorted result on {} input of size {}",
+                            algorithm.name,
+                            shape.name(),
+                            size
+                        ));
+                    }
+                }
+                records.push(Record {
+                    algorithm: algorithm.name,
+                    shape,
+                    size,
+                    stats: Stats::from_samples(samples),
+                });
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn micros(d: Duration) -> f64 {
+    d.as_secs_f64() * 1e6
+}
+
+fn render_table(records: &[Record]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<20} {:<11} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "algorithm", "shape", "size", "min(us)", "median(us)", "p75(us)", "p90(us)", "max(us)"
+    );
+    for r in records {
+        let _ = writeln!(
+            out,
+            "{:<20} {:<11} {:>8} {:>12.1} {:>12.1} {:>12.1} {:>12.1} {:>12.1}",
+            r.algorithm,
+            r.shape.name(),
+            r.size,
+            micros(r.stats.min),
+            micros(r.stats.median),
+            micros(r.stats.p75),
+            micros(r.stats.p90),
+            micros(r.stats.max)
+        );
+    }
+    out
+}
+
+fn render_csv(records: &[Record]) -> String {
+    let mut out =
+        String::from("algorithm,shape,size,min_ns,p25_ns,median_ns,p75_ns,p90_ns,max_ns\n");
+    for r in records {
+        let s = &r.stats;
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            r.algorithm,
+            r.shape.name(),
+            r.size,
+            s.min.as_nanos(),
+            s.p25.as_nanos(),
+            s.median.as_nanos(),
+            s.p75.as_nanos(),
+            s.p90.as_nanos(),
+            s.max.as_nanos()
+        );
+    }
+    out
+}
+
+fn render_json(config: &Config, records: &[Record]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{{\n  \"seed\": {},\n  \"runs\": {},\n  \"warmup\": {},\n  \"results\": [",
+        config.seed, config.runs, config.warmup
+    );
+    for (i, r) in records.iter().enumerate() {
+        let s = &r.stats;
+        let _ = write!(
+            out,
+            "    {{\"algorithm\": \"{}\", \"shape\": \"{}\", \"size\": {}, \
+             \"min_ns\": {}, \"p25_ns\": {}, \"median_ns\": {}, \"p75_ns\": {}, \
+             \"p90_ns\": {}, \"max_ns\": {}}}",
+            r.algorithm,
+            r.shape.name(),
+            r.size,
+            s.min.as_nanos(),
+            s.p25.as_nanos(),
+            s.median.as_nanos(),
+            s.p75.as_nanos(),
+            s.p90.as_nanos(),
+            s.max.as_nanos()
+        );
+        out.push_str(if i + 1 < records.len() { ",\n" } else { "\n" });
+    }
+    out.push_str("  ]\n}\n");
+    out
+}
+
+/// write `content` to `path`, or to stdout if `path` is `-`
+fn emit(path: &str, content: &str) -> Result<(), String> {
+    if path == "-" {
+        print!("{}", content);
+        return Ok(());
+    }
+    std::fs::write(path, content).map_err(|e| format!("cannot write `{}`: {}", path, e))
+}
+
+/// run the benchmark on threads with enough stack, then print and write the results
+fn bench(config: Config) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .stack_size(STACK_SIZE)
+        .build_global()
+        .map_err(|e| e.to_string())?;
+    let (config, records) = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(&config).map(|records| (config, records)))
+        .map_err(|e| e.to_string())?
+        .join()
+        .map_err(|_| "benchmark thread panicked".to_string())??;
+
+    print!("{}", render_table(&records));
+    if let Some(path) = &config.csv {
+        emit(path, &render_csv(&records))?;
+    }
+    if let Some(path) = &config.json {
+        emit(path, &render_json(&config, &records))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let config = match Config::parse(std::env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    };
+
+    if let Err(e) = bench(config) {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_shapes() {
+        let mut rng = RandomGen::new(1);
+        assert_eq!(Shape::Sorted.generate(4, &mut rng), vec![0, 1, 2, 3]);
+        assert_eq!(Shape::Reversed.generate(4, &mut rng), vec![3, 2, 1, 0]);
+        assert_eq!(
+            Shape::Sawtooth.generate(6, &mut rng),
+            vec![0, 1, 0, 1, 0, 1]
+        );
+        assert_eq!(Shape::OrganPipe.generate(5, &mut rng), vec![0, 1, 2, 1, 0]);
+        assert!(Shape::FewUnique
+            .generate(100, &mut rng)
+            .iter()
+            .all(|&x| x < 8));
+        // the same seed always generates the same input
+        let a = Shape::Random.generate(50, &mut RandomGen::new(7));
+        let b = Shape::Random.generate(50, &mut RandomGen::new(7));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<Duration> = (1..=10).map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples.into_iter().rev().collect());
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p90, Duration::from_millis(9));
+        assert_eq!(stats.max, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(args("--sizes 10,20 --shapes sorted --runs 3"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.sizes, vec![10, 20]);
+        assert_eq!(config.shapes, vec![Shape::Sorted]);
+        assert_eq!(config.runs, 3);
+        assert_eq!(Config::parse(args("--help")), Ok(None));
+        assert!(Config::parse(args("--shapes zigzag")).is_err());
+        assert!(Config::parse(args("--runs")).is_err());
+    }
+
+    #[test]
+    fn test_run_every_algorithm() {
+        let config = Config {
+            sizes: vec![0, 1, 37],
+            runs: 2,
+            ..Config::default()
+        };
+        let records = run(&config).unwrap();
+        assert_eq!(records.len(), 3 * Shape::ALL.len() * ALGORITHMS.len());
+        assert!(render_csv(&records).lines().count() == records.len() + 1);
+        assert!(render_json(&config, &records).contains("\"shape\": \"organ-pipe\""));
+    }
+}
@@ -0,0 +1,322 @@
+//! Throughput benchmark of the single-producer single-consumer queue.
+//!
+//! Moves `--items` integers from a producer thread to a consumer thread through
+//! `spsc_queue` (one element at a time and in batches), and through the locking
+//! `BlockingQueue` and `std::sync::mpsc::sync_channel` of the same capacity for
+//! comparison, and prints the median time and throughput of each.
+//!
+//! ```text
+//! cargo run --release --bin spsc_bench -- --items 10000000 --capacity 1024 --batch 64
+//! ```
+
+use rs_algorithm_practise::data_structure::blocking_queue::BlockingQueue;
+use rs_algorithm_practise::data_structure::spsc_queue;
+use std::hint::{black_box, spin_loop};
+use std::process::exit;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: spsc_bench [OPTIONS]
+
+Options:
+  --items <N>              items moved per run (default: 1000000)
+  --capacity <N>           capacity of every queue (default: 1024)
+  --batch <N>              elements per `push_slice` / `pop_slice` (default: 64)
+  --runs <N>               timed runs per queue (default: 5)
+  -h, --help               print this message";
+
+/// settings of a benchmark session, parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    items: usize,
+    capacity: usize,
+    batch: usize,
+    runs: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            items: 1_000_000,
+            capacity: 1024,
+            batch: 64,
+            runs: 5,
+        }
+    }
+}
+
+impl Config {
+    /// parse the command line arguments (without the program name).
+    /// returns `Ok(None)` if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Config>, String> {
+        let mut config = Config::default();
+        while let Some(flag) = args.next() {
+            if flag == "-h" || flag == "--help" {
+                return Ok(None);
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for `{}`", flag))?;
+            let value = parse_number(&value)?;
+            match flag.as_str() {
+                "--items" => config.items = value,
+                "--capacity" => config.capacity = value,
+                "--batch" => config.batch = value,
+                "--runs" => config.runs = value,
+                _ => return Err(format!("unknown option `{}`", flag)),
+            }
+        }
+        if config.capacity == 0 || config.batch == 0 || config.runs == 0 {
+            return Err("`--capacity`, `--batch` and `--runs` must be at least 1".to_string());
+        }
+        Ok(Some(config))
+    }
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.parse().map_err(|_| format!("invalid number `{}`", s))
+}
+
+/// waits for the other thread by spinning a little, then yielding, so the
+/// benchmark also finishes on a machine with a single core
+#[derive(Default)]
+struct Backoff {
+    spins: u32,
+}
+
+impl Backoff {
+    fn wait(&mut self) {
+        if self.spins < 64 {
+            self.spins += 1;
+            spin_loop();
+        } else {
+            thread::yield_now();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.spins = 0;
+    }
+}
+
+/// move `0..items` from a producer thread to the calling thread, and return the
+/// elapsed time. `consume` must return the sum of the items it received
+fn transfer<P>(items: usize, produce: P, consume: impl FnOnce() -> u64) -> Duration
+where
+    P: FnOnce() + Send + 'static,
+{
+    let start = Instant::now();
+    let producer = thread::spawn(produce);
+    let sum = consume();
+    producer.join().expect("the producer thread panicked");
+    let elapsed = start.elapsed();
+    let n = items as u64;
+    assert_eq!(sum, n * n.saturating_sub(1) / 2, "items were lost");
+    elapsed
+}
+
+fn spsc_single(config: &Config) -> Duration {
+    let items = config.items;
+    let (mut producer, mut consumer) = spsc_queue::channel(config.capacity);
+    let produce = move || {
+        let mut backoff = Backoff::default();
+        for i in 0..items as u64 {
+            while producer.push(i).is_err() {
+                backoff.wait();
+            }
+            backoff.reset();
+        }
+    };
+    let consume = move || {
+        let mut backoff = Backoff::default();
+        let mut sum = 0;
+        for _ in 0..items {
+            loop {
+                if let Some(x) = consumer.pop() {
+                    sum += black_box(x);
+                    break;
+                }
+                backoff.wait();
+            }
+            backoff.reset();
+        }
+        sum
+    };
+    transfer(items, produce, consume)
+}
+
+fn spsc_batch(config: &Config) -> Duration {
+    let (items, batch) = (config.items, config.batch);
+    let (mut producer, mut consumer) = spsc_queue::channel(config.capacity);
+    let produce = move || {
+        let mut backoff = Backoff::default();
+        let mut buffer = vec![0u64; batch];
+        let mut next = 0;
+        while next < items {
+            let len = batch.min(items - next);
+            for (i, slot) in buffer[..len].iter_mut().enumerate() {
+                *slot = (next + i) as u64;
+            }
+            let mut sent = 0;
+            while sent < len {
+                match producer.push_slice(&buffer[sent..len]) {
+                    0 => backoff.wait(),
+                    pushed => {
+                        sent += pushed;
+                        backoff.reset();
+                    }
+                }
+            }
+            next += len;
+        }
+    };
+    let consume = move || {
+        let mut backoff = Backoff::default();
+        let mut buffer = vec![0u64; batch];
+        let mut sum = 0;
+        let mut received = 0;
+        while received < items {
+            match consumer.pop_slice(&mut buffer) {
+                0 => backoff.wait(),
+                popped => {
+                    sum += buffer[..popped].iter().map(|&x| black_box(x)).sum::<u64>();
+                    received += popped;
+                    backoff.reset();
+                }
+            }
+        }
+        sum
+    };
+    transfer(items, produce, consume)
+}
+
+fn blocking_queue(config: &Config) -> Duration {
+    let items = config.items;
+    let queue = Arc::new(BlockingQueue::new(config.capacity));
+    let sender = Arc::clone(&queue);
+    let produce = move || {
+        for i in 0..items as u64 {
+            sender.put(i).expect("the queue is not closed");
+        }
+    };
+    let consume = move || {
+        (0..items)
+            .map(|_| black_box(queue.take().expect("the queue is not closed")))
+            .sum()
+    };
+    transfer(items, produce, consume)
+}
+
+fn sync_channel(config: &Config) -> Duration {
+    let items = config.items;
+    let (sender, receiver) = mpsc::sync_channel(config.capacity);
+    let produce = move || {
+        for i in 0..items as u64 {
+            sender.send(i).expect("the receiver is alive");
+        }
+    };
+    let consume = move || receiver.iter().map(black_box).sum();
+    transfer(items, produce, consume)
+}
+
+/// a queue to compare, and how to move the items of a run through it
+struct Queue {
+    name: &'static str,
+    run: fn(&Config) -> Duration,
+}
+
+const QUEUES: [Queue; 4] = [
+    Queue {
+        name: "spsc_queue",
+        run: spsc_single,
+    },
+    Queue {
+        name: "spsc_queue (batch)",
+        run: spsc_batch,
+    },
+    Queue {
+        name: "BlockingQueue",
+        run: blocking_queue,
+    },
+    Queue {
+        name: "mpsc::sync_channel",
+        run: sync_channel,
+    },
+];
+
+fn bench(config: &Config) {
+    println!(
+        "{} items, capacity {}, batch {}, {} runs\n",
+        config.items, config.capacity, config.batch, config.runs
+    );
+    println!("{:<20} {:>14} {:>16}", "queue", "median", "items/s");
+    for queue in &QUEUES {
+        let mut samples: Vec<Duration> = (0..config.runs).map(|_| (queue.run)(config)).collect();
+        samples.sort();
+        let median = samples[samples.len() / 2];
+        let throughput = config.items as f64 / median.as_secs_f64().max(f64::EPSILON);
+        println!(
+            "{:<20} {:>14} {:>16.0}",
+            queue.name,
+            format!("{:.3?}", median),
+            throughput
+        );
+    }
+}
+
+fn main() {
+    let config = match Config::parse(std::env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    };
+    bench(&config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Config::parse(args("")), Ok(Some(Config::default())));
+        assert_eq!(Config::parse(args("--runs 1 -h")), Ok(None));
+        assert_eq!(
+            Config::parse(args("--items 100 --capacity 8 --batch 3")),
+            Ok(Some(Config {
+                items: 100,
+                capacity: 8,
+                batch: 3,
+                ..Config::default()
+            }))
+        );
+        assert!(Config::parse(args("--capacity 0")).is_err());
+        assert!(Config::parse(args("--items ten")).is_err());
+        assert!(Config::parse(args("--items")).is_err());
+    }
+
+    #[test]
+    fn test_every_queue_moves_all_items() {
+        let config = Config {
+            items: 10_000,
+            capacity: 16,
+            batch: 5,
+            runs: 1,
+        };
+        for queue in &QUEUES {
+            (queue.run)(&config);
+        }
+    }
+}
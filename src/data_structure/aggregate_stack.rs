@@ -0,0 +1,228 @@
+use super::heap::{Max, Min};
+use super::stack::Stack;
+use std::ops::Add;
+
+/// an associative operation that combines the elements of an [`AggregateStack`],
+/// e.g. minimum, maximum, sum or gcd.
+///
+/// it is implemented by the markers [`Min`], [`Max`] and [`Sum`], and by any
+/// closure `Fn(&T, &T) -> T`.
+pub trait Aggregate<T> {
+    /// combine the aggregate of the elements below with a new element
+    fn combine(&self, below: &T, value: &T) -> T;
+}
+
+impl<T: Ord + Clone> Aggregate<T> for Min {
+    #[inline]
+    fn combine(&self, below: &T, value: &T) -> T {
+        below.min(value).clone()
+    }
+}
+
+impl<T: Ord + Clone> Aggregate<T> for Max {
+    #[inline]
+    fn combine(&self, below: &T, value: &T) -> T {
+        below.max(value).clone()
+    }
+}
+
+/// aggregates the elements by adding them up
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Sum;
+
+impl<T: Add<Output = T> + Clone> Aggregate<T> for Sum {
+    #[inline]
+    fn combine(&self, below: &T, value: &T) -> T {
+        below.clone() + value.clone()
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> T> Aggregate<T> for F {
+    #[inline]
+    fn combine(&self, below: &T, value: &T) -> T {
+        self(below, value)
+    }
+}
+
+pub type MinStack<T> = AggregateStack<T, Min>;
+pub type MaxStack<T> = AggregateStack<T, Max>;
+
+/// a stack that answers the aggregate of all its elements in O(1).
+///
+/// every element is stored together with the aggregate of itself and all the
+/// elements below it, so `push`, `pop` and `aggregate` are all O(1).
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::aggregate_stack::{AggregateStack, MinStack};
+///
+/// let mut stack = MinStack::new();
+/// stack.push(3);
+/// stack.push(1);
+/// stack.push(2);
+/// assert_eq!(stack.min(), Some(&1));
+/// stack.pop();
+/// stack.pop();
+/// assert_eq!(stack.min(), Some(&3));
+///
+/// let mut sums = AggregateStack::with_aggregate(|a: &i32, b: &i32| a + b);
+/// sums.push(1);
+/// sums.push(2);
+/// assert_eq!(sums.aggregate(), Some(&3));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateStack<T, A> {
+    // (element, aggregate of the element and all elements below)
+    elements: Stack<(T, T)>,
+    op: A,
+}
+
+impl<T: Clone, A: Aggregate<T>> AggregateStack<T, A> {
+    /// create an empty stack that aggregates its elements with `op`
+    #[inline]
+    pub fn with_aggregate(op: A) -> Self {
+        AggregateStack {
+            elements: Stack::new(),
+            op,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let aggregate = match self.elements.peek() {
+            Some((_, below)) => self.op.combine(below, &item),
+            None => item.clone(),
+        };
+        self.elements.push((item, aggregate));
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.elements.pop().map(|(item, _)| item)
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.peek().map(|(item, _)| item)
+    }
+
+    /// the aggregate of all elements, or `None` if the stack is empty
+    #[inline]
+    pub fn aggregate(&self) -> Option<&T> {
+        self.elements.peek().map(|(_, aggregate)| aggregate)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.elements.size()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.elements.clear()
+    }
+
+    /// iterate over the elements from the top to the bottom
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements.iter().map(|(item, _)| item)
+    }
+}
+
+impl<T: Ord + Clone> MinStack<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_aggregate(Min)
+    }
+
+    /// the smallest element, in O(1)
+    #[inline]
+    pub fn min(&self) -> Option<&T> {
+        self.aggregate()
+    }
+}
+
+impl<T: Ord + Clone> Default for MinStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> MaxStack<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_aggregate(Max)
+    }
+
+    /// the largest element, in O(1)
+    #[inline]
+    pub fn max(&self) -> Option<&T> {
+        self.aggregate()
+    }
+}
+
+impl<T: Ord + Clone> Default for MaxStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_stack() {
+        let mut stack = MinStack::new();
+        assert_eq!(stack.min(), None);
+        for x in [5, 3, 4, 1, 2] {
+            stack.push(x);
+        }
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.min(), Some(&3));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &3, &5]);
+    }
+
+    #[test]
+    fn test_max_stack() {
+        let mut stack = MaxStack::default();
+        for x in [1, 3, 2] {
+            stack.push(x);
+        }
+        assert_eq!(stack.max(), Some(&3));
+        assert_eq!(stack.peek(), Some(&2));
+        stack.pop();
+        stack.pop();
+        assert_eq!(stack.max(), Some(&1));
+        stack.clear();
+        assert_eq!(stack.max(), None);
+    }
+
+    #[test]
+    fn test_custom_aggregate() {
+        fn gcd(a: &u64, b: &u64) -> u64 {
+            let (mut a, mut b) = (*a, *b);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        }
+
+        let mut stack = AggregateStack::with_aggregate(gcd);
+        stack.push(12);
+        stack.push(18);
+        assert_eq!(stack.aggregate(), Some(&6));
+        stack.push(9);
+        assert_eq!(stack.aggregate(), Some(&3));
+        stack.pop();
+        assert_eq!(stack.aggregate(), Some(&6));
+        assert_eq!(stack.size(), 2);
+    }
+}
@@ -0,0 +1,464 @@
+use super::ring_queue::RingQueue;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// the error returned when an element could not be put into a [`BlockingQueue`].
+/// it gives the element back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutError<T> {
+    /// the queue was full, for `try_put`, or still full at the timeout, for `offer`
+    Full(T),
+    /// the queue was closed
+    Closed(T),
+}
+
+impl<T> PutError<T> {
+    /// take the element back
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            PutError::Full(item) | PutError::Closed(item) => item,
+        }
+    }
+}
+
+impl<T> Display for PutError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PutError::Full(_) => write!(f, "the queue is full"),
+            PutError::Closed(_) => write!(f, "the queue is closed"),
+        }
+    }
+}
+
+impl<T: Debug> Error for PutError<T> {}
+
+/// the error returned when no element could be taken from a [`BlockingQueue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TakeError {
+    /// the queue was empty, for `try_take`, or still empty at the timeout, for `poll`
+    Empty,
+    /// the queue was closed and empty, so no element will ever come
+    Closed,
+}
+
+impl Display for TakeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TakeError::Empty => write!(f, "the queue is empty"),
+            TakeError::Closed => write!(f, "the queue is closed"),
+        }
+    }
+}
+
+impl Error for TakeError {}
+
+struct State<T> {
+    items: RingQueue<T>,
+    closed: bool,
+}
+
+/// a bounded thread-safe FIFO queue for many producers and many consumers.
+///
+/// producers block in `put` while the queue is full, and consumers block in
+/// `take` while it is empty. the state is guarded by a `Mutex`, and the waiting
+/// threads sleep on two `Condvar`s, one for each condition, so a producer only
+/// wakes up consumers and the other way around.
+///
+/// after `close`, every waiting thread wakes up, nothing can be put any more,
+/// and the consumers can still take the elements left in the queue.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::blocking_queue::BlockingQueue;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let queue = Arc::new(BlockingQueue::new(2));
+/// let producer = {
+///     let queue = Arc::clone(&queue);
+///     thread::spawn(move || {
+///         for i in 0..10 {
+///             queue.put(i).unwrap(); // blocks while the consumer is behind
+///         }
+///         queue.close();
+///     })
+/// };
+///
+/// let mut received = Vec::new();
+/// while let Some(item) = queue.take() {
+///     received.push(item);
+/// }
+/// producer.join().unwrap();
+/// assert_eq!(received, (0..10).collect::<Vec<_>>());
+/// ```
+pub struct BlockingQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> BlockingQueue<T> {
+    /// create an empty queue that holds at most `capacity` elements.
+    ///
+    /// # Panics
+    /// if `capacity` is 0
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "the capacity of a blocking queue must be positive"
+        );
+        Self::with_state(capacity, RingQueue::with_capacity(capacity))
+    }
+
+    /// create an empty queue without a capacity, `put` never blocks
+    pub fn unbounded() -> Self {
+        Self::with_state(usize::MAX, RingQueue::new())
+    }
+
+    fn with_state(capacity: usize, items: RingQueue<T>) -> Self {
+        BlockingQueue {
+            state: Mutex::new(State {
+                items,
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// put an element at the back, waiting while the queue is full.
+    ///
+    /// # Errors
+    /// [`PutError::Closed`] if the queue is closed, before or while waiting
+    pub fn put(&self, item: T) -> Result<(), PutError<T>> {
+        let state = self.lock();
+        let state = self
+            .not_full
+            .wait_while(state, |s| s.items.len() >= self.capacity && !s.closed)
+            .unwrap_or_else(PoisonError::into_inner);
+        self.push(state, item)
+    }
+
+    /// put an element at the back, waiting at most `timeout` while the queue is full.
+    ///
+    /// # Errors
+    /// [`PutError::Full`] if the queue is still full at the timeout,
+    /// [`PutError::Closed`] if the queue is closed
+    pub fn offer(&self, item: T, timeout: Duration) -> Result<(), PutError<T>> {
+        let state = self.lock();
+        let (state, _) = self
+            .not_full
+            .wait_timeout_while(state, timeout, |s| {
+                s.items.len() >= self.capacity && !s.closed
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        self.push(state, item)
+    }
+
+    /// put an element at the back if the queue is not full, without waiting.
+    ///
+    /// # Errors
+    /// [`PutError::Full`] if the queue is full, [`PutError::Closed`] if it is closed
+    pub fn try_put(&self, item: T) -> Result<(), PutError<T>> {
+        self.push(self.lock(), item)
+    }
+
+    /// take the front element, waiting while the queue is empty.
+    /// returns `None` once the queue is closed and empty.
+    pub fn take(&self) -> Option<T> {
+        let state = self.lock();
+        let state = self
+            .not_empty
+            .wait_while(state, |s| s.items.is_empty() && !s.closed)
+            .unwrap_or_else(PoisonError::into_inner);
+        self.pop(state).ok()
+    }
+
+    /// take the front element, waiting at most `timeout` while the queue is empty.
+    ///
+    /// # Errors
+    /// [`TakeError::Empty`] if the queue is still empty at the timeout,
+    /// [`TakeError::Closed`] if it is closed and empty
+    pub fn poll(&self, timeout: Duration) -> Result<T, TakeError> {
+        let state = self.lock();
+        let (state, _) = self
+            .not_empty
+            .wait_timeout_while(state, timeout, |s| s.items.is_empty() && !s.closed)
+            .unwrap_or_else(PoisonError::into_inner);
+        self.pop(state)
+    }
+
+    /// take the front element if there is one, without waiting.
+    ///
+    /// # Errors
+    /// [`TakeError::Empty`] if the queue is empty,
+    /// [`TakeError::Closed`] if it is closed and empty
+    pub fn try_take(&self) -> Result<T, TakeError> {
+        self.pop(self.lock())
+    }
+
+    /// move all the elements to `target`, in order, without waiting.
+    /// returns the number of elements moved.
+    #[inline]
+    pub fn drain_to<E: Extend<T>>(&self, target: &mut E) -> usize {
+        self.drain_to_max(target, usize::MAX)
+    }
+
+    /// move at most `max` elements from the front to `target`, in order, without waiting.
+    /// returns the number of elements moved.
+    pub fn drain_to_max<E: Extend<T>>(&self, target: &mut E, max: usize) -> usize {
+        let mut drained = Vec::new();
+        {
+            let mut state = self.lock();
+            while drained.len() < max {
+                match state.items.pop_front() {
+                    Some(item) => drained.push(item),
+                    None => break,
+                }
+            }
+        }
+        if !drained.is_empty() {
+            self.not_full.notify_all();
+        }
+        // extend outside of the lock, `target` may do anything
+        let count = drained.len();
+        target.extend(drained);
+        count
+    }
+
+    /// close the queue: `put` fails from now on, and all waiting threads wake up.
+    /// the elements in the queue can still be taken.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// the number of elements at the time of the call
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    /// whether the queue was empty at the time of the call
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    /// the maximum number of elements, `usize::MAX` if the queue is unbounded
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // the state is valid even if a thread panicked while holding the lock,
+    // since no user code runs under the lock
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, mut state: MutexGuard<'_, State<T>>, item: T) -> Result<(), PutError<T>> {
+        if state.closed {
+            return Err(PutError::Closed(item));
+        }
+        if state.items.len() >= self.capacity {
+            return Err(PutError::Full(item));
+        }
+        state.items.push_back(item);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn pop(&self, mut state: MutexGuard<'_, State<T>>) -> Result<T, TakeError> {
+        match state.items.pop_front() {
+            Some(item) => {
+                drop(state);
+                self.not_full.notify_one();
+                Ok(item)
+            }
+            None if state.closed => Err(TakeError::Closed),
+            None => Err(TakeError::Empty),
+        }
+    }
+}
+
+impl<T> Debug for BlockingQueue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("BlockingQueue")
+            .field("len", &state.items.len())
+            .field("capacity", &self.capacity)
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn test_try_operations() {
+        let queue = BlockingQueue::new(2);
+        assert_eq!(queue.try_take(), Err(TakeError::Empty));
+        assert_eq!(queue.try_put(1), Ok(()));
+        assert_eq!(queue.try_put(2), Ok(()));
+        assert_eq!(queue.try_put(3), Err(PutError::Full(3)));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.try_take(), Ok(1));
+
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.try_put(4).unwrap_err().into_inner(), 4);
+        // the elements left can still be taken
+        assert_eq!(queue.try_take(), Ok(2));
+        assert_eq!(queue.try_take(), Err(TakeError::Closed));
+        assert_eq!(queue.take(), None);
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let queue = BlockingQueue::new(1);
+        let start = Instant::now();
+        assert_eq!(queue.poll(Duration::from_millis(20)), Err(TakeError::Empty));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        queue.put('a').unwrap();
+        let start = Instant::now();
+        assert_eq!(
+            queue.offer('b', Duration::from_millis(20)),
+            Err(PutError::Full('b'))
+        );
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(queue.poll(Duration::from_millis(20)), Ok('a'));
+    }
+
+    #[test]
+    fn test_offer_succeeds_when_space_frees_up() {
+        let queue = Arc::new(BlockingQueue::new(1));
+        queue.put(1).unwrap();
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                queue.take()
+            })
+        };
+        assert_eq!(queue.offer(2, Duration::from_secs(10)), Ok(()));
+        assert_eq!(consumer.join().unwrap(), Some(1));
+        assert_eq!(queue.take(), Some(2));
+    }
+
+    #[test]
+    fn test_close_wakes_up_waiters() {
+        let empty = Arc::new(BlockingQueue::<i32>::new(1));
+        let full = Arc::new(BlockingQueue::new(1));
+        full.put(0).unwrap();
+
+        let takers: Vec<_> = (0..3)
+            .map(|_| {
+                let queue = Arc::clone(&empty);
+                thread::spawn(move || queue.take())
+            })
+            .collect();
+        let putters: Vec<_> = (1..=3)
+            .map(|i| {
+                let queue = Arc::clone(&full);
+                thread::spawn(move || queue.put(i))
+            })
+            .collect();
+
+        thread::sleep(Duration::from_millis(20));
+        empty.close();
+        full.close();
+        for taker in takers {
+            assert_eq!(taker.join().unwrap(), None);
+        }
+        for putter in putters {
+            assert!(matches!(putter.join().unwrap(), Err(PutError::Closed(_))));
+        }
+        assert_eq!(full.take(), Some(0));
+    }
+
+    #[test]
+    fn test_many_producers_and_consumers() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const N: usize = 5_000;
+        let queue = Arc::new(BlockingQueue::new(16));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..N {
+                        queue.put(p * N + i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    while let Some(item) = queue.take() {
+                        received.push(item);
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+        let mut received: Vec<usize> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        received.sort();
+        assert_eq!(received, (0..PRODUCERS * N).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drain_to() {
+        let queue = BlockingQueue::unbounded();
+        for i in 0..10 {
+            queue.put(i).unwrap();
+        }
+        let mut batch = Vec::new();
+        assert_eq!(queue.drain_to_max(&mut batch, 3), 3);
+        assert_eq!(batch, vec![0, 1, 2]);
+
+        let mut rest = RingQueue::new();
+        assert_eq!(queue.drain_to(&mut rest), 7);
+        assert_eq!(rest, (3..10).collect());
+        assert!(queue.is_empty());
+        assert_eq!(queue.drain_to(&mut rest), 0);
+        assert_eq!(
+            format!("{:?}", queue),
+            format!(
+                "BlockingQueue {{ len: 0, capacity: {}, closed: false }}",
+                usize::MAX
+            )
+        );
+    }
+}
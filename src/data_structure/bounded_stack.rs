@@ -0,0 +1,483 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// what a bounded stack does when an element is pushed onto a full stack
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// refuse the new element, `push` returns an [`OverflowError`] holding it
+    #[default]
+    Reject,
+    /// remove the bottom (oldest) element to make room, e.g. for undo histories
+    DropOldest,
+    /// panic, e.g. for call stacks where an overflow is a bug
+    Panic,
+}
+
+/// the error returned when pushing onto a full stack.
+/// it gives the rejected element back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError<T> {
+    pub capacity: usize,
+    pub item: T,
+}
+
+impl<T> OverflowError<T> {
+    /// take the rejected element back
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T> Display for OverflowError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stack overflow: the capacity is {}", self.capacity)
+    }
+}
+
+impl<T: Debug> Error for OverflowError<T> {}
+
+/// a stack with a maximum depth, and an [`OverflowPolicy`] that decides
+/// what happens when an element is pushed onto a full stack.
+///
+/// the elements are stored in a `VecDeque`, so dropping the oldest
+/// element is O(1) as well.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::bounded_stack::{BoundedStack, OverflowPolicy};
+///
+/// // an undo history that keeps the last two edits
+/// let mut history = BoundedStack::new(2, OverflowPolicy::DropOldest);
+/// assert_eq!(history.push("type a"), Ok(None));
+/// assert_eq!(history.push("type b"), Ok(None));
+/// assert_eq!(history.push("delete"), Ok(Some("type a")));
+/// assert_eq!(history.pop(), Some("delete"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundedStack<T> {
+    elements: VecDeque<T>,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> BoundedStack<T> {
+    /// create an empty stack that holds at most `capacity` elements.
+    ///
+    /// `capacity` is only a limit, the storage grows as elements are pushed.
+    #[inline]
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        BoundedStack {
+            elements: VecDeque::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    /// create an empty stack that holds at most `capacity` elements,
+    /// with room for `preallocate` of them (at most `capacity`) allocated up front
+    #[inline]
+    pub fn with_capacity(capacity: usize, policy: OverflowPolicy, preallocate: usize) -> Self {
+        BoundedStack {
+            elements: VecDeque::with_capacity(preallocate.min(capacity)),
+            capacity,
+            policy,
+        }
+    }
+
+    /// push an element, applying the overflow policy if the stack is full.
+    ///
+    /// # Returns
+    /// `Ok(None)` if the element was pushed without overflow,
+    /// `Ok(Some(oldest))` if the oldest element was dropped to make room,
+    /// and `Err(OverflowError)` if the element was rejected.
+    ///
+    /// # Panics
+    /// panic if the stack is full and the policy is [`OverflowPolicy::Panic`]
+    pub fn push(&mut self, item: T) -> Result<Option<T>, OverflowError<T>> {
+        if !self.is_full() {
+            self.elements.push_back(item);
+            return Ok(None);
+        }
+        match self.policy {
+            OverflowPolicy::Reject => Err(self.overflow(item)),
+            OverflowPolicy::DropOldest => match self.elements.pop_front() {
+                Some(oldest) => {
+                    self.elements.push_back(item);
+                    Ok(Some(oldest))
+                }
+                // a stack of capacity zero drops the new element right away
+                None => Ok(Some(item)),
+            },
+            OverflowPolicy::Panic => panic!("{}", self.overflow(item)),
+        }
+    }
+
+    /// push an element if the stack is not full, whatever the overflow policy is
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), OverflowError<T>> {
+        if self.is_full() {
+            return Err(self.overflow(item));
+        }
+        self.elements.push_back(item);
+        Ok(())
+    }
+
+    #[inline]
+    fn overflow(&self, item: T) -> OverflowError<T> {
+        OverflowError {
+            capacity: self.capacity,
+            item,
+        }
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.elements.pop_back()
+    }
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.back()
+    }
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.elements.back_mut()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.elements.len() >= self.capacity
+    }
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+    /// the maximum number of elements
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    #[inline]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.elements.clear()
+    }
+    /// iterate over the elements from the top to the bottom
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.elements.iter().rev()
+    }
+}
+
+/// a bounded stack stored in an array of `N` slots, which never allocates.
+///
+/// it supports the same overflow policies as [`BoundedStack`]. the slots are
+/// used as a ring, so dropping the oldest element is O(1).
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::bounded_stack::{ArrayStack, OverflowPolicy};
+///
+/// let mut calls: ArrayStack<&str, 2> = ArrayStack::new(OverflowPolicy::Reject);
+/// calls.try_push("main").unwrap();
+/// calls.try_push("parse").unwrap();
+/// let err = calls.try_push("eval").unwrap_err();
+/// assert_eq!(err.into_inner(), "eval");
+/// assert_eq!(calls.pop(), Some("parse"));
+/// ```
+#[derive(Clone)]
+pub struct ArrayStack<T, const N: usize> {
+    slots: [Option<T>; N],
+    // index of the bottom element
+    bottom: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T, const N: usize> ArrayStack<T, N> {
+    #[inline]
+    pub fn new(policy: OverflowPolicy) -> Self {
+        ArrayStack {
+            slots: std::array::from_fn(|_| None),
+            bottom: 0,
+            len: 0,
+            policy,
+        }
+    }
+
+    // the slot of the i-th element from the bottom
+    #[inline]
+    fn slot(&self, i: usize) -> usize {
+        (self.bottom + i) % N
+    }
+
+    /// push an element, applying the overflow policy if the stack is full,
+    /// see [`BoundedStack::push`]
+    ///
+    /// # Panics
+    /// panic if the stack is full and the policy is [`OverflowPolicy::Panic`]
+    pub fn push(&mut self, item: T) -> Result<Option<T>, OverflowError<T>> {
+        if !self.is_full() {
+            let slot = self.slot(self.len);
+            self.slots[slot] = Some(item);
+            self.len += 1;
+            return Ok(None);
+        }
+        match self.policy {
+            OverflowPolicy::Reject => Err(OverflowError { capacity: N, item }),
+            // the stack is full, so the slot of the bottom element is the next free slot
+            OverflowPolicy::DropOldest if N > 0 => {
+                let oldest = self.slots[self.bottom].replace(item);
+                self.bottom = self.slot(1);
+                Ok(oldest)
+            }
+            OverflowPolicy::DropOldest => Ok(Some(item)),
+            OverflowPolicy::Panic => panic!("{}", OverflowError { capacity: N, item }),
+        }
+    }
+
+    /// push an element if the stack is not full, whatever the overflow policy is
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), OverflowError<T>> {
+        if self.is_full() {
+            return Err(OverflowError { capacity: N, item });
+        }
+        let slot = self.slot(self.len);
+        self.slots[slot] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.slot(self.len);
+        self.slots[slot].take()
+    }
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        match self.len {
+            0 => None,
+            len => self.slots[self.slot(len - 1)].as_ref(),
+        }
+    }
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        match self.len {
+            0 => None,
+            len => {
+                let slot = self.slot(len - 1);
+                self.slots[slot].as_mut()
+            }
+        }
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+    #[inline]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+        self.bottom = 0;
+    }
+    /// iterate over the elements from the top to the bottom
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        (0..self.len).rev().map(move |i| {
+            self.slots[self.slot(i)]
+                .as_ref()
+                .expect("the slots of the elements are filled")
+        })
+    }
+}
+
+/// compares the elements and the policies, wherever the ring of slots starts
+impl<T: PartialEq, const N: usize> PartialEq for ArrayStack<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.policy == other.policy && self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayStack<T, N> {}
+
+/// shows the elements from the bottom to the top, like [`BoundedStack`]
+impl<T: Debug, const N: usize> Debug for ArrayStack<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArrayStack")
+            .field("elements", &self.iter().rev().collect::<Vec<_>>())
+            .field("capacity", &N)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<T, const N: usize> Default for ArrayStack<T, N> {
+    fn default() -> Self {
+        Self::new(OverflowPolicy::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject() {
+        let mut stack = BoundedStack::new(2, OverflowPolicy::Reject);
+        assert_eq!(stack.push(1), Ok(None));
+        assert_eq!(stack.push(2), Ok(None));
+        assert!(stack.is_full());
+
+        let err = stack.push(3).unwrap_err();
+        assert_eq!(err.to_string(), "stack overflow: the capacity is 2");
+        assert_eq!(err.into_inner(), 3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    }
+
+    #[test]
+    fn test_drop_oldest() {
+        let mut stack = BoundedStack::new(3, OverflowPolicy::DropOldest);
+        for i in 0..5 {
+            stack.push(i).unwrap();
+        }
+        assert_eq!(stack.size(), 3);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), None);
+
+        // try_push rejects even with this policy
+        let mut stack = BoundedStack::new(1, OverflowPolicy::DropOldest);
+        stack.try_push(1).unwrap();
+        assert_eq!(stack.try_push(2).unwrap_err().item, 2);
+
+        let mut stack = BoundedStack::new(0, OverflowPolicy::DropOldest);
+        assert_eq!(stack.push(1), Ok(Some(1)));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "stack overflow")]
+    fn test_panic() {
+        let mut stack = BoundedStack::new(1, OverflowPolicy::Panic);
+        let _ = stack.push(1);
+        let _ = stack.push(2);
+    }
+
+    #[test]
+    fn test_capacity_is_only_a_limit() {
+        let mut stack = BoundedStack::new(usize::MAX, OverflowPolicy::Panic);
+        assert_eq!(stack.push(1), Ok(None));
+        assert!(!stack.is_full());
+        assert_eq!(stack.capacity(), usize::MAX);
+        assert_eq!(stack.pop(), Some(1));
+
+        let mut stack = BoundedStack::with_capacity(2, OverflowPolicy::Reject, 100);
+        assert_eq!(stack.push(1), Ok(None));
+        assert_eq!(stack.push(2), Ok(None));
+        assert_eq!(stack.push(3).unwrap_err().into_inner(), 3);
+    }
+
+    #[test]
+    fn test_array_stack() {
+        let mut stack: ArrayStack<i32, 3> = ArrayStack::default();
+        assert_eq!(stack.policy(), OverflowPolicy::Reject);
+        assert_eq!(stack.peek(), None);
+        stack.try_push(1).unwrap();
+        stack.try_push(2).unwrap();
+        *stack.peek_mut().unwrap() += 10;
+        assert_eq!(stack.peek(), Some(&12));
+        stack.try_push(3).unwrap();
+        assert!(stack.is_full());
+        assert_eq!(
+            stack.push(4),
+            Err(OverflowError {
+                capacity: 3,
+                item: 4
+            })
+        );
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &12, &1]);
+
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn test_array_stack_drop_oldest() {
+        let mut stack: ArrayStack<i32, 3> = ArrayStack::new(OverflowPolicy::DropOldest);
+        for i in 0..3 {
+            assert_eq!(stack.push(i), Ok(None));
+        }
+        assert_eq!(stack.push(3), Ok(Some(0)));
+        assert_eq!(stack.push(4), Ok(Some(1)));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&4, &3, &2]);
+        assert_eq!(stack.pop(), Some(4));
+        // the ring wraps around the end of the array
+        assert_eq!(stack.push(5), Ok(None));
+        assert_eq!(stack.push(6), Ok(Some(2)));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&6, &5, &3]);
+
+        let mut empty: ArrayStack<i32, 0> = ArrayStack::new(OverflowPolicy::DropOldest);
+        assert_eq!(empty.push(1), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_array_stack_eq_and_debug() {
+        // the same elements, with the ring starting at different slots
+        let mut wrapped: ArrayStack<i32, 2> = ArrayStack::new(OverflowPolicy::DropOldest);
+        let mut straight: ArrayStack<i32, 2> = ArrayStack::new(OverflowPolicy::DropOldest);
+        for i in 1..=3 {
+            let _ = wrapped.push(i);
+        }
+        for i in 2..=3 {
+            let _ = straight.push(i);
+        }
+        assert_eq!(wrapped, straight);
+        assert_eq!(
+            format!("{:?}", wrapped),
+            "ArrayStack { elements: [2, 3], capacity: 2, policy: DropOldest }"
+        );
+        assert_eq!(format!("{:?}", wrapped), format!("{:?}", straight));
+
+        straight.pop();
+        assert_ne!(wrapped, straight);
+        let mut rejecting: ArrayStack<i32, 2> = ArrayStack::new(OverflowPolicy::Reject);
+        rejecting.try_push(2).unwrap();
+        rejecting.try_push(3).unwrap();
+        assert_ne!(wrapped, rejecting);
+    }
+
+    #[test]
+    #[should_panic(expected = "stack overflow")]
+    fn test_array_stack_panic() {
+        let mut stack: ArrayStack<i32, 1> = ArrayStack::new(OverflowPolicy::Panic);
+        let _ = stack.push(1);
+        let _ = stack.push(2);
+    }
+}
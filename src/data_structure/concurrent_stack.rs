@@ -0,0 +1,414 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// retired nodes are freed once there are at least this many of them
+const SCAN_THRESHOLD: usize = 64;
+
+struct Node<T> {
+    // taken by the thread that unlinks the node
+    data: ManuallyDrop<T>,
+    // never changes after the node is published
+    next: *mut Node<T>,
+    // link of the retired list, only used after the node is unlinked
+    retired_next: *mut Node<T>,
+}
+
+/// a hazard pointer: a node that a thread is reading, and that must not be freed.
+/// records are reused by other threads, and only freed with the stack.
+struct HazardRecord {
+    ptr: AtomicPtr<()>,
+    active: AtomicBool,
+    // never changes after the record is published
+    next: *mut HazardRecord,
+}
+
+/// a hazard record owned by the current thread until it is dropped
+struct HazardGuard<'a> {
+    record: &'a HazardRecord,
+}
+
+impl HazardGuard<'_> {
+    #[inline]
+    fn protect<T>(&self, node: *mut Node<T>) {
+        self.record.ptr.store(node as *mut (), Ordering::SeqCst);
+    }
+}
+
+impl Drop for HazardGuard<'_> {
+    fn drop(&mut self) {
+        self.record.ptr.store(ptr::null_mut(), Ordering::Release);
+        self.record.active.store(false, Ordering::Release);
+    }
+}
+
+/// a lock-free concurrent stack (Treiber stack).
+///
+/// `push` and `pop` take `&self` and update the top of the stack with a single
+/// compare-and-swap, retrying if another thread got there first, so it can be
+/// shared between threads, e.g. through an `Arc`.
+///
+/// popped nodes cannot be freed right away, because another thread may still be
+/// reading them. they are reclaimed with hazard pointers: before reading a node, a
+/// thread publishes its address in a hazard record, and retired nodes are only
+/// freed when no hazard record holds them. this also prevents the ABA problem, as
+/// the address of a node cannot be reused while a thread is about to swap it.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::concurrent_stack::ConcurrentStack;
+/// use std::sync::Arc;
+///
+/// let stack = Arc::new(ConcurrentStack::new());
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         let stack = Arc::clone(&stack);
+///         std::thread::spawn(move || stack.push(i))
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// let mut items = Vec::new();
+/// while let Some(item) = stack.pop() {
+///     items.push(item);
+/// }
+/// items.sort();
+/// assert_eq!(items, vec![0, 1, 2, 3]);
+/// ```
+pub struct ConcurrentStack<T> {
+    head: AtomicPtr<Node<T>>,
+    hazards: AtomicPtr<HazardRecord>,
+    retired: AtomicPtr<Node<T>>,
+    retired_count: AtomicUsize,
+    // the stack owns the elements
+    phantom: PhantomData<T>,
+}
+
+// SAFETY: the elements are moved between threads, but never shared between them:
+// only the thread that unlinks a node reads its data.
+unsafe impl<T: Send> Send for ConcurrentStack<T> {}
+unsafe impl<T: Send> Sync for ConcurrentStack<T> {}
+
+impl<T> ConcurrentStack<T> {
+    #[inline]
+    pub fn new() -> Self {
+        ConcurrentStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            hazards: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            retired_count: AtomicUsize::new(0),
+            phantom: PhantomData,
+        }
+    }
+
+    /// whether the stack was empty at the time of the call
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    /// push an element onto the top of the stack
+    pub fn push(&self, data: T) {
+        let node = Box::into_raw(Box::new(Node {
+            data: ManuallyDrop::new(data),
+            next: ptr::null_mut(),
+            retired_next: ptr::null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is not published yet, so this thread owns it
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// remove the top element of the stack and return it,
+    /// or `None` if the stack is empty
+    pub fn pop(&self) -> Option<T> {
+        let hazard = self.acquire_hazard();
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if head.is_null() {
+                return None;
+            }
+            // protect the node, then check that it was not unlinked (and maybe
+            // freed) before the protection became visible
+            hazard.protect(head);
+            if self.head.load(Ordering::SeqCst) != head {
+                continue;
+            }
+            // SAFETY: the node is protected, so it has not been freed
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                hazard.protect::<T>(ptr::null_mut());
+                // SAFETY: this thread unlinked the node, so no other thread reads its
+                // data, and the data is taken only once
+                let data = unsafe { ptr::read(ptr::addr_of!((*head).data)) };
+                self.retire(head);
+                return Some(ManuallyDrop::into_inner(data));
+            }
+        }
+    }
+
+    // find an inactive hazard record to reuse, or publish a new one
+    fn acquire_hazard(&self) -> HazardGuard<'_> {
+        let mut record = self.hazards.load(Ordering::Acquire);
+        while !record.is_null() {
+            // SAFETY: records are only freed with the stack
+            let r = unsafe { &*record };
+            if !r.active.load(Ordering::Relaxed)
+                && r.active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return HazardGuard { record: r };
+            }
+            record = r.next;
+        }
+
+        let record = Box::into_raw(Box::new(HazardRecord {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.hazards.load(Ordering::Acquire);
+        loop {
+            // SAFETY: the record is not published yet, so this thread owns it
+            unsafe { (*record).next = head };
+            match self.hazards.compare_exchange_weak(
+                head,
+                record,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                // SAFETY: records are only freed with the stack
+                Ok(_) => {
+                    return HazardGuard {
+                        record: unsafe { &*record },
+                    }
+                }
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    // add an unlinked node to the retired list, and free the retired nodes
+    // that are not protected if there are enough of them
+    fn retire(&self, node: *mut Node<T>) {
+        // count the node before publishing it, so a scan never frees more
+        // nodes than were counted
+        let count = self.retired_count.fetch_add(1, Ordering::Relaxed) + 1;
+        self.push_retired(node);
+        if count >= SCAN_THRESHOLD {
+            self.scan();
+        }
+    }
+
+    fn push_retired(&self, node: *mut Node<T>) {
+        let mut head = self.retired.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is unlinked, and only the retiring thread writes `retired_next`
+            unsafe { (*node).retired_next = head };
+            match self.retired.compare_exchange_weak(
+                head,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    fn scan(&self) {
+        // take the whole retired list, so no other thread scans the same nodes
+        let mut node = self.retired.swap(ptr::null_mut(), Ordering::Acquire);
+        // a hazard published before a node was unlinked is visible after this fence
+        fence(Ordering::SeqCst);
+
+        let mut protected = Vec::new();
+        let mut record = self.hazards.load(Ordering::Acquire);
+        while !record.is_null() {
+            // SAFETY: records are only freed with the stack
+            let r = unsafe { &*record };
+            let p = r.ptr.load(Ordering::SeqCst);
+            if !p.is_null() {
+                protected.push(p);
+            }
+            record = r.next;
+        }
+
+        let mut freed = 0;
+        while !node.is_null() {
+            // SAFETY: the nodes in the taken list are only accessed by this thread
+            let next = unsafe { (*node).retired_next };
+            if protected.contains(&(node as *mut ())) {
+                self.push_retired(node);
+            } else {
+                // SAFETY: the node is unlinked and no thread protects it, so no thread
+                // can read it any more. its data was taken, `ManuallyDrop` does not drop it
+                drop(unsafe { Box::from_raw(node) });
+                freed += 1;
+            }
+            node = next;
+        }
+        self.retired_count.fetch_sub(freed, Ordering::Relaxed);
+    }
+}
+
+impl<T> Default for ConcurrentStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for ConcurrentStack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConcurrentStack")
+            .field("is_empty", &self.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for ConcurrentStack<T> {
+    fn drop(&mut self) {
+        // `&mut self` means no other thread can access the stack any more
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            // SAFETY: the node is still in the stack, so it owns its data
+            let mut b = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut b.data) };
+            node = b.next;
+        }
+        let mut node = *self.retired.get_mut();
+        while !node.is_null() {
+            // SAFETY: the data of retired nodes was taken when they were popped
+            let b = unsafe { Box::from_raw(node) };
+            node = b.retired_next;
+        }
+        let mut record = *self.hazards.get_mut();
+        while !record.is_null() {
+            // SAFETY: no guard can be alive, since guards borrow the stack
+            let b = unsafe { Box::from_raw(record) };
+            record = b.next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    // counts how many times it is dropped
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_stack() {
+        let stack = ConcurrentStack::new();
+        assert!(stack.is_empty());
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert!(!stack.is_empty());
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<ConcurrentStack<String>>();
+    }
+
+    #[test]
+    fn test_concurrent_push_then_pop() {
+        const THREADS: usize = 8;
+        const N: usize = 10_000;
+        let stack = Arc::new(ConcurrentStack::new());
+
+        let pushers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || (0..N).for_each(|i| stack.push(t * N + i)))
+            })
+            .collect();
+        pushers.into_iter().for_each(|h| h.join().unwrap());
+
+        let poppers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    while let Some(x) = stack.pop() {
+                        popped.push(x);
+                    }
+                    popped
+                })
+            })
+            .collect();
+        let mut popped: Vec<usize> = poppers
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        popped.sort();
+        assert_eq!(popped, (0..THREADS * N).collect::<Vec<_>>());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_push_and_pop() {
+        const THREADS: usize = 8;
+        const N: usize = 20_000;
+        let drops = Arc::new(AtomicUsize::new(0));
+        let stack = Arc::new(ConcurrentStack::new());
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                let drops = Arc::clone(&drops);
+                thread::spawn(move || {
+                    let mut popped = 0;
+                    for i in 0..N {
+                        stack.push(DropCounter(Arc::clone(&drops)));
+                        if i % 3 != 0 && stack.pop().is_some() {
+                            popped += 1;
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+        let popped: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        // the popped elements were dropped right away
+        assert_eq!(drops.load(Ordering::SeqCst), popped);
+
+        // the elements left in the stack are dropped with it, exactly once
+        drop(stack);
+        assert_eq!(drops.load(Ordering::SeqCst), THREADS * N);
+    }
+}
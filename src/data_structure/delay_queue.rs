@@ -0,0 +1,273 @@
+use super::heap::PriorityQueue;
+use super::slab::{Key, Slab};
+use crate::supplementary::clock::{Clock, SystemClock};
+use std::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
+
+// the deadline of an item, ordered by deadline and then by insertion order
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Expiration {
+    deadline: Instant,
+    seq: u64,
+    key: Key,
+}
+
+/// a queue that yields its items only after their deadline, the earliest
+/// deadline first, e.g. to schedule retries with backoff.
+///
+/// the deadlines are kept in a [`PriorityQueue`], and the items in a [`Slab`], so
+/// every insert returns a [`Key`] to cancel the item with `remove`. a cancelled
+/// item is removed at once, and its deadline is dropped from the heap when it
+/// reaches the top, or when the cancelled deadlines outnumber the live ones.
+///
+/// the time is read from a [`Clock`], the system clock by default. nothing runs
+/// in the background: `poll_expired` returns an expired item if there is one,
+/// and `wait` sleeps until the next deadline.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::delay_queue::DelayQueue;
+/// use rs_algorithm_practise::supplementary::clock::FakeClock;
+/// use std::time::Duration;
+///
+/// let clock = FakeClock::new();
+/// let mut retries = DelayQueue::with_clock(clock.clone());
+/// retries.insert("fetch", Duration::from_secs(2));
+/// let upload = retries.insert("upload", Duration::from_secs(1));
+/// retries.insert("report", Duration::from_secs(4));
+///
+/// assert_eq!(retries.poll_expired(), None);
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(retries.remove(upload), Some("upload"));
+/// assert_eq!(retries.poll_expired(), Some("fetch"));
+/// assert_eq!(retries.poll_expired(), None);
+///
+/// // sleeping on a fake clock moves it to the deadline
+/// assert_eq!(retries.wait(), Some("report"));
+/// assert!(retries.is_empty());
+/// ```
+pub struct DelayQueue<T, C = SystemClock> {
+    expirations: PriorityQueue<Expiration>,
+    items: Slab<T>,
+    seq: u64,
+    clock: C,
+}
+
+impl<T> DelayQueue<T> {
+    /// create an empty queue on the system clock
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: Clock> DelayQueue<T, C> {
+    /// create an empty queue that reads the time from `clock`
+    #[inline]
+    pub fn with_clock(clock: C) -> Self {
+        DelayQueue {
+            expirations: PriorityQueue::new(),
+            items: Slab::new(),
+            seq: 0,
+            clock,
+        }
+    }
+
+    /// insert an item that expires after `delay`, and return the key to cancel it
+    ///
+    /// # Panics
+    /// if `delay` is too large for an `Instant`, e.g. `Duration::MAX`
+    #[inline]
+    pub fn insert(&mut self, item: T, delay: Duration) -> Key {
+        let deadline = self
+            .clock
+            .now()
+            .checked_add(delay)
+            .expect("the delay of a delay queue item is too large");
+        self.insert_at(item, deadline)
+    }
+
+    /// insert an item that expires at `deadline`, and return the key to cancel it.
+    /// items with the same deadline expire in the order they were inserted
+    pub fn insert_at(&mut self, item: T, deadline: Instant) -> Key {
+        let key = self.items.insert(item);
+        self.expirations.push(Expiration {
+            deadline,
+            seq: self.seq,
+            key,
+        });
+        self.seq += 1;
+        key
+    }
+
+    /// cancel the item of `key` and return it, or `None` if it already expired
+    /// or was cancelled
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let item = self.items.remove(key)?;
+        // rebuild the heap from the live deadlines, so cancelled ones do not pile up
+        if self.expirations.len() > 2 * self.items.len() + 32 {
+            let expirations = std::mem::replace(&mut self.expirations, PriorityQueue::new());
+            for expiration in expirations.into_inner() {
+                if self.items.contains(expiration.key) {
+                    self.expirations.push(expiration);
+                }
+            }
+        }
+        Some(item)
+    }
+
+    /// whether the item of `key` is still waiting in the queue
+    #[inline]
+    pub fn contains(&self, key: Key) -> bool {
+        self.items.contains(key)
+    }
+
+    /// remove and return the item with the earliest deadline if it has expired
+    pub fn poll_expired(&mut self) -> Option<T> {
+        let deadline = self.next_deadline()?;
+        if deadline > self.clock.now() {
+            return None;
+        }
+        let expiration = self.expirations.pop()?;
+        self.items.remove(expiration.key)
+    }
+
+    /// the earliest deadline of the items, or `None` if the queue is empty
+    pub fn next_deadline(&mut self) -> Option<Instant> {
+        // drop the cancelled deadlines from the top
+        while let Some(expiration) = self.expirations.peek() {
+            if self.items.contains(expiration.key) {
+                return Some(expiration.deadline);
+            }
+            self.expirations.pop();
+        }
+        None
+    }
+
+    /// block until the item with the earliest deadline expires and return it,
+    /// or return `None` at once if the queue is empty
+    pub fn wait(&mut self) -> Option<T> {
+        loop {
+            let deadline = self.next_deadline()?;
+            self.clock.sleep_until(deadline);
+            if let Some(item) = self.poll_expired() {
+                return Some(item);
+            }
+        }
+    }
+
+    /// the number of items waiting in the queue
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.expirations = PriorityQueue::new();
+    }
+
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+}
+
+impl<T, C> Debug for DelayQueue<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DelayQueue")
+            .field("len", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supplementary::clock::FakeClock;
+
+    #[test]
+    fn test_expire_in_deadline_order() {
+        let clock = FakeClock::new();
+        let mut queue = DelayQueue::with_clock(clock.clone());
+        for (item, delay) in [(3, 30), (1, 10), (2, 20), (4, 20), (5, 50)] {
+            queue.insert(item, Duration::from_millis(delay));
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(
+            queue.next_deadline(),
+            Some(clock.now() + Duration::from_millis(10))
+        );
+        assert_eq!(queue.poll_expired(), None);
+
+        clock.advance(Duration::from_millis(20));
+        assert_eq!(queue.poll_expired(), Some(1));
+        // the same deadline expires in insertion order
+        assert_eq!(queue.poll_expired(), Some(2));
+        assert_eq!(queue.poll_expired(), Some(4));
+        assert_eq!(queue.poll_expired(), None);
+
+        let start = clock.now();
+        assert_eq!(queue.wait(), Some(3));
+        assert_eq!(clock.now() - start, Duration::from_millis(10));
+        assert_eq!(queue.wait(), Some(5));
+        assert_eq!(queue.wait(), None);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let clock = FakeClock::new();
+        let mut queue = DelayQueue::with_clock(clock.clone());
+        let keys: Vec<Key> = (0..1000)
+            .map(|i| queue.insert(i, Duration::from_secs(i)))
+            .collect();
+        // cancel every item but the multiples of 100
+        for (i, key) in keys.iter().enumerate() {
+            if i % 100 != 0 {
+                assert_eq!(queue.remove(*key), Some(i as u64));
+            }
+        }
+        assert_eq!(queue.len(), 10);
+        // the cancelled deadlines do not pile up
+        assert!(queue.expirations.len() <= 2 * queue.len() + 32);
+        assert!(!queue.contains(keys[1]));
+        assert!(queue.contains(keys[100]));
+
+        clock.advance(Duration::from_secs(500));
+        let expired: Vec<u64> = std::iter::from_fn(|| queue.poll_expired()).collect();
+        assert_eq!(expired, vec![0, 100, 200, 300, 400, 500]);
+        assert_eq!(queue.remove(keys[500]), None);
+        queue.clear();
+        assert_eq!(queue.wait(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the delay of a delay queue item is too large")]
+    fn test_huge_delay() {
+        let mut queue = DelayQueue::with_clock(FakeClock::new());
+        queue.insert("a day", Duration::from_secs(86_400));
+        queue.insert("never", Duration::MAX);
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let mut queue = DelayQueue::new();
+        let start = Instant::now();
+        queue.insert("late", Duration::from_millis(20));
+        queue.insert("now", Duration::ZERO);
+        assert_eq!(queue.poll_expired(), Some("now"));
+        assert_eq!(queue.wait(), Some("late"));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -1,15 +1,26 @@
 use std::{
     cell::RefCell,
+    cmp::Ordering,
     fmt::Display,
+    marker::PhantomData,
     rc::{Rc, Weak},
 };
 
 /// a doubly linked list
-#[derive(Default, Debug, Clone)]
+///
+/// The nodes are only reachable through the list that owns them, and are only
+/// borrowed mutably by methods taking `&mut self`. So the references into the
+/// nodes handed out by methods like [`DoublyLinkedList::iter`] can never alias
+/// a `RefMut`, and are created with `RefCell::as_ptr` instead of a `Ref` guard.
+#[derive(Default, Debug)]
 pub struct DoublyLinkedList<T> {
     first: Option<Rc<RefCell<ListNode<T>>>>,
     last: Option<Weak<RefCell<ListNode<T>>>>,
+    len: usize,
 }
+type Node<T> = Rc<RefCell<ListNode<T>>>;
+type Link<T> = Option<Node<T>>;
+
 /// list node of doubly linked list
 #[derive(Default, Debug, Clone)]
 pub struct ListNode<T> {
@@ -29,6 +40,7 @@ impl<T> DoublyLinkedList<T> {
         DoublyLinkedList {
             first: None,
             last: None,
+            len: 0,
         }
     }
     /// Checks whether the doubly linked list is empty.
@@ -41,6 +53,162 @@ impl<T> DoublyLinkedList<T> {
         self.first.is_none() && self.last.is_none()
     }
 
+    /// Returns the number of elements in the list, in `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a reference to the first element, or `None` if the list is empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: see the invariant on `DoublyLinkedList`
+        self.first
+            .as_ref()
+            .map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns a mutable reference to the first element, or `None` if the list is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: see the invariant on `DoublyLinkedList`
+        self.first
+            .as_ref()
+            .map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns a reference to the last element, or `None` if the list is empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: see the invariant on `DoublyLinkedList`, and `last` points to
+        // a node owned by the list
+        self.last
+            .as_ref()
+            .map(|node| unsafe { &(*(*node.as_ptr()).as_ptr()).data })
+    }
+
+    /// Returns a mutable reference to the last element, or `None` if the list is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `back`
+        self.last
+            .as_ref()
+            .map(|node| unsafe { &mut (*(*node.as_ptr()).as_ptr()).data })
+    }
+
+    /// Returns a cursor at the first element, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.first.clone(),
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.last.as_ref().and_then(Weak::upgrade),
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the first element that can edit the list,
+    /// see [`CursorMut`].
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.first.clone(),
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element that can edit the list,
+    /// see [`CursorMut`].
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.last.as_ref().and_then(Weak::upgrade),
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns an iterator over the elements, from the front to the back.
+    /// It can also be walked from the back with `rev` or `next_back`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+    /// use rs_algorithm_practise::doubly_linked_list;
+    ///
+    /// let mut dll = doubly_linked_list![1, 2, 3];
+    /// for x in dll.iter_mut() {
+    ///     *x *= 10;
+    /// }
+    /// assert_eq!(dll.iter().collect::<Vec<_>>(), vec![&10, &20, &30]);
+    /// assert_eq!(dll.iter().rev().collect::<Vec<_>>(), vec![&30, &20, &10]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.first.as_deref(),
+            // SAFETY: `last` points to a node owned by the list
+            back: self.last.as_ref().map(|node| unsafe { &*node.as_ptr() }),
+            len: self.len,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements, from the front to the back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.first.as_deref(),
+            // SAFETY: `last` points to a node owned by the list
+            back: self.last.as_ref().map(|node| unsafe { &*node.as_ptr() }),
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes all elements, in `O(n)`.
+    pub fn clear(&mut self) {
+        // pop the nodes one by one, dropping the first node would drop
+        // the rest of the list recursively
+        while self.pop_front().is_some() {}
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`, in order.
+    /// The nodes of the removed elements are unlinked, the others are not moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+    /// use rs_algorithm_practise::doubly_linked_list;
+    ///
+    /// let mut dll = doubly_linked_list![1, 2, 3, 4];
+    /// dll.retain(|x| x % 2 == 0);
+    /// assert_eq!(dll, doubly_linked_list![2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut node = self.first.clone();
+        while let Some(current) = node {
+            node = current.borrow().next.clone();
+            if !keep(&current.borrow().data) {
+                self.unlink(&current);
+            }
+        }
+    }
+
     /// Adds a new element at the front of the list.
     ///
     /// # Arguments
@@ -68,6 +236,7 @@ impl<T> DoublyLinkedList<T> {
                 self.first = Some(new_node);
             }
         }
+        self.len += 1;
     }
 
     /// Removes the first element from the list and returns it.
@@ -114,6 +283,7 @@ impl<T> DoublyLinkedList<T> {
                     self.last = None;
                 }
             }
+            self.len -= 1;
             first_node.data
         })
     }
@@ -146,6 +316,7 @@ impl<T> DoublyLinkedList<T> {
                 self.first = Some(new_node);
             }
         }
+        self.len += 1;
     }
 
     /// Remove the last element of the list and returns it
@@ -197,76 +368,801 @@ impl<T> DoublyLinkedList<T> {
                 .ok()
                 .expect("DoublyLinkedList::pop_back(): Rc has more than one strong reference")
                 .into_inner();
+            self.len -= 1;
             last_node.data
         })
     }
+
+    /// Sorts the list in place with a bottom-up merge sort.
+    ///
+    /// The sort is stable and takes `O(n log n)` time. It relinks the existing
+    /// nodes, so no element is moved or reallocated.
+    ///
+    /// # Arguments
+    ///
+    /// - `compare: F` - A function that returns the ordering of two elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+    /// use rs_algorithm_practise::doubly_linked_list;
+    ///
+    /// let mut dll = doubly_linked_list![1, 3, 2];
+    /// dll.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(dll, doubly_linked_list![3, 2, 1]);
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // runs[i] is either empty or a sorted run of 2^i nodes,
+        // and runs in higher slots hold earlier nodes
+        let mut runs: [Link<T>; usize::BITS as usize] = std::array::from_fn(|_| None);
+
+        let mut rest = self.take_chain();
+        while let Some(node) = rest {
+            rest = node.borrow_mut().next.take();
+            let mut carry = Some(node);
+            let mut i = 0;
+            while let Some(run) = runs[i].take() {
+                carry = merge_chains(Some(run), carry, &mut compare);
+                i += 1;
+            }
+            runs[i] = carry;
+        }
+
+        let mut res = None;
+        for run in runs {
+            res = merge_chains(run, res, &mut compare);
+        }
+        self.set_chain(res);
+    }
+
+    /// Sorts the list in place with insertion sort.
+    ///
+    /// The sort is stable and takes `O(n^2)` time, which is fine for short lists.
+    /// Like [`DoublyLinkedList::sort_by`], it only relinks the nodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `compare: F` - A function that returns the ordering of two elements.
+    pub fn insertion_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut sorted: Link<T> = None;
+        let mut rest = self.take_chain();
+        while let Some(node) = rest {
+            rest = node.borrow_mut().next.take();
+            // find the last node that is not greater than the new one
+            let mut prev: Link<T> = None;
+            let mut cursor = sorted.clone();
+            while let Some(current) = cursor {
+                if compare(&current.borrow().data, &node.borrow().data) == Ordering::Greater {
+                    break;
+                }
+                cursor = current.borrow().next.clone();
+                prev = Some(current);
+            }
+            match prev {
+                Some(prev) => {
+                    node.borrow_mut().next = prev.borrow_mut().next.take();
+                    prev.borrow_mut().next = Some(node);
+                }
+                None => {
+                    node.borrow_mut().next = sorted.take();
+                    sorted = Some(node);
+                }
+            }
+        }
+        self.set_chain(sorted);
+    }
+
+    /// Merges another sorted list into this sorted list, keeping it sorted.
+    ///
+    /// The nodes of `other` are relinked into this list. On equal elements,
+    /// the elements of this list come first.
+    ///
+    /// # Arguments
+    ///
+    /// - `other: DoublyLinkedList<T>` - A list sorted by the same `compare` function.
+    /// - `compare: F` - A function that returns the ordering of two elements.
+    pub fn merge_by<F>(&mut self, mut other: DoublyLinkedList<T>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let res = merge_chains(self.take_chain(), other.take_chain(), &mut compare);
+        self.set_chain(res);
+    }
+
+    // take the nodes out of the list as a chain linked by `next` only
+    fn take_chain(&mut self) -> Link<T> {
+        self.last = None;
+        self.len = 0;
+        self.first.take()
+    }
+
+    // make a chain linked by `next` the content of the list,
+    // restoring the `prev` pointers, the last node and the length
+    fn set_chain(&mut self, chain: Link<T>) {
+        let mut prev: Option<Weak<RefCell<ListNode<T>>>> = None;
+        let mut len = 0;
+        let mut node = chain.clone();
+        while let Some(current) = node {
+            current.borrow_mut().prev = prev;
+            prev = Some(Rc::downgrade(&current));
+            node = current.borrow().next.clone();
+            len += 1;
+        }
+        self.first = chain;
+        self.last = prev;
+        self.len = len;
+    }
+
+    // take all the nodes out of the list, as its first and last node and its length
+    fn take_ends(&mut self) -> Option<(Node<T>, Node<T>, usize)> {
+        let len = self.len;
+        let last = self.last.take().and_then(|last| last.upgrade());
+        let first = self.take_chain();
+        first.zip(last).map(|(first, last)| (first, last, len))
+    }
+
+    // unlink a node of this list, linking its neighbours to each other
+    fn unlink(&mut self, node: &Rc<RefCell<ListNode<T>>>) {
+        let mut node = node.borrow_mut();
+        let prev = node.prev.take().and_then(|prev| prev.upgrade());
+        let next = node.next.take();
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.last = prev.as_ref().map(Rc::downgrade),
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.first = next,
+        }
+        self.len -= 1;
+    }
 }
 
-impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
-    fn eq(&self, other: &Self) -> bool {
-        // init two pointers
-        let mut a = self.first.clone();
-        let mut b = other.first.clone();
-        // compare the elements in two lists one by one
-        loop {
-            match (a, b) {
-                (None, None) => return true,     // both None, it is equal
-                (None, Some(_)) => return false, // one None one Some, not equal
-                (Some(_), None) => return false,
-                (Some(a_rc), Some(b_rc)) => {
-                    // both Some, compare the value inside Option
-                    let a_ref = a_rc.borrow();
-                    let b_ref = b_rc.borrow();
-
-                    if a_ref.data != b_ref.data {
-                        return false;
-                    }
-                    // move both pointers to next nodes
-                    a = a_ref.next.clone();
-                    b = b_ref.next.clone();
+impl<T: PartialEq> DoublyLinkedList<T> {
+    /// Returns `true` if the list contains an element equal to `x`, in `O(n)`.
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool {
+        self.iter().any(|item| item == x)
+    }
+}
+
+impl<T: Ord> DoublyLinkedList<T> {
+    /// Sorts the list in ascending order, see [`DoublyLinkedList::sort_by`].
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp)
+    }
+
+    /// Sorts the list in ascending order, see [`DoublyLinkedList::insertion_sort_by`].
+    #[inline]
+    pub fn insertion_sort(&mut self) {
+        self.insertion_sort_by(T::cmp)
+    }
+
+    /// Merges another list sorted in ascending order into this one,
+    /// see [`DoublyLinkedList::merge_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+    /// use rs_algorithm_practise::doubly_linked_list;
+    ///
+    /// let mut dll = doubly_linked_list![1, 4, 5];
+    /// dll.merge(doubly_linked_list![2, 3, 6]);
+    /// assert_eq!(dll, doubly_linked_list![1, 2, 3, 4, 5, 6]);
+    /// ```
+    #[inline]
+    pub fn merge(&mut self, other: DoublyLinkedList<T>) {
+        self.merge_by(other, T::cmp)
+    }
+}
+
+/// Merges two sorted chains linked by `next` only, relinking their nodes.
+/// On equal elements, the nodes of `a` come first.
+fn merge_chains<T, F>(mut a: Link<T>, mut b: Link<T>, compare: &mut F) -> Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head: Link<T> = None;
+    let mut tail: Link<T> = None;
+    loop {
+        let node = match (a.take(), b.take()) {
+            (Some(x), Some(y)) => {
+                if compare(&y.borrow().data, &x.borrow().data) == Ordering::Less {
+                    b = y.borrow_mut().next.take();
+                    a = Some(x);
+                    y
+                } else {
+                    a = x.borrow_mut().next.take();
+                    b = Some(y);
+                    x
+                }
+            }
+            // one of the chains is exhausted, append the other one
+            (rest, None) | (None, rest) => {
+                match &tail {
+                    Some(tail) => tail.borrow_mut().next = rest,
+                    None => head = rest,
                 }
+                return head;
             }
+        };
+        match &tail {
+            Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+            None => head = Some(node.clone()),
         }
+        tail = Some(node);
     }
 }
 
+// the node before `node`, borrowed for as long as `node` is
+fn prev_node<T>(node: &ListNode<T>) -> Option<&RefCell<ListNode<T>>> {
+    // SAFETY: the previous node owns `node`, so it lives at least as long
+    node.prev.as_ref().map(|prev| unsafe { &*prev.as_ptr() })
+}
+
+/// An iterator over the elements of a [`DoublyLinkedList`], from the front to the back.
+pub struct Iter<'a, T> {
+    next: Option<&'a RefCell<ListNode<T>>>,
+    back: Option<&'a RefCell<ListNode<T>>>,
+    // the number of elements left between `next` and `back`, both included
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.next.map(|node| {
+            // SAFETY: see the invariant on `DoublyLinkedList`
+            let node = unsafe { &*node.as_ptr() };
+            self.next = node.next.as_deref();
+            self.len -= 1;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| {
+            // SAFETY: see the invariant on `DoublyLinkedList`
+            let node = unsafe { &*node.as_ptr() };
+            self.back = prev_node(node);
+            self.len -= 1;
+            &node.data
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a [`DoublyLinkedList`],
+/// from the front to the back.
+pub struct IterMut<'a, T> {
+    next: Option<&'a RefCell<ListNode<T>>>,
+    back: Option<&'a RefCell<ListNode<T>>>,
+    // the number of elements left between `next` and `back`, both included
+    len: usize,
+    // the iterator borrows the list mutably
+    phantom: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.next.map(|node| {
+            let node = node.as_ptr();
+            // SAFETY: see the invariant on `DoublyLinkedList`, and every node is
+            // visited once, so the mutable references are unique
+            unsafe {
+                self.next = (*node).next.as_deref();
+                self.len -= 1;
+                &mut (*node).data
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| {
+            let node = node.as_ptr();
+            // SAFETY: as in `next`, `next` and `back` stop when they meet, as
+            // `len` counts the nodes between them
+            unsafe {
+                self.back = prev_node(&*node);
+                self.len -= 1;
+                &mut (*node).data
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a [`DoublyLinkedList`], from the front to the back.
+pub struct IntoIter<T> {
+    list: DoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Pushes the items to the back in order.
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Pushes the items to the back in order.
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+/// A cursor over a [`DoublyLinkedList`], that can move forward and backward.
+///
+/// A cursor points at an element, or at the "ghost" position between the back and
+/// the front of the list, where it is after moving past either end. Moving from
+/// the ghost position goes to the front (with `move_next`) or to the back (with
+/// `move_prev`), so the list is walked like a ring.
+pub struct Cursor<'a, T> {
+    current: Link<T>,
+    // the index of the current element, or the length of the list at the ghost position
+    index: usize,
+    list: &'a DoublyLinkedList<T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the index of the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    /// Moves to the next element, from the back to the ghost position,
+    /// and from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.borrow().next.clone();
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.first.clone();
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves to the previous element, from the front to the ghost position,
+    /// and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+                self.index = match self.current {
+                    Some(_) => self.index - 1,
+                    None => self.list.len,
+                };
+            }
+            None => {
+                self.current = self.list.last.as_ref().and_then(Weak::upgrade);
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: see the invariant on `DoublyLinkedList`, the list is borrowed for `'a`
+        self.current
+            .as_ref()
+            .map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns the next element, the front at the ghost position.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match &self.current {
+            Some(node) => node.borrow().next.clone(),
+            None => self.list.first.clone(),
+        };
+        // SAFETY: see the invariant on `DoublyLinkedList`, the list is borrowed for `'a`
+        next.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns the previous element, the back at the ghost position.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match &self.current {
+            Some(node) => node.borrow().prev.clone(),
+            None => self.list.last.clone(),
+        };
+        // SAFETY: see the invariant on `DoublyLinkedList`, the list is borrowed for `'a`
+        prev.and_then(|node| node.upgrade())
+            .map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+}
+
+/// A cursor over a [`DoublyLinkedList`] that can also edit the list around it.
+///
+/// Besides moving like a [`Cursor`], it inserts and removes elements at its
+/// position, and splits or splices whole lists there. Every edit only relinks
+/// the nodes next to the cursor, so each one takes `O(1)`.
+///
+/// # Examples
+///
+/// ```
+/// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
+/// use rs_algorithm_practise::doubly_linked_list;
+///
+/// // a text buffer, edited at the cursor
+/// let mut text: DoublyLinkedList<char> = "helo world".chars().collect();
+/// let mut cursor = text.cursor_front_mut();
+/// cursor.move_next();
+/// cursor.move_next();
+/// cursor.insert_after('l');
+/// // move to the space, and cut everything after it
+/// while cursor.current() != Some(&mut ' ') {
+///     cursor.move_next();
+/// }
+/// let world = cursor.split_after();
+/// cursor.remove_current();
+/// // paste it at the front, with a new space
+/// cursor.move_next();
+/// let mut space = doubly_linked_list![' '];
+/// space.cursor_front_mut().splice_before(world);
+/// cursor.splice_before(space);
+/// assert_eq!(text.iter().collect::<String>(), "world hello");
+/// ```
+pub struct CursorMut<'a, T> {
+    current: Link<T>,
+    // the index of the current element, or the length of the list at the ghost position
+    index: usize,
+    list: &'a mut DoublyLinkedList<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the index of the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    /// Returns a read-only cursor at the same position.
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.current.clone(),
+            index: self.index,
+            list: self.list,
+        }
+    }
+
+    /// Moves to the next element, see [`Cursor::move_next`].
+    pub fn move_next(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_next();
+        (self.current, self.index) = (cursor.current, cursor.index);
+    }
+
+    /// Moves to the previous element, see [`Cursor::move_prev`].
+    pub fn move_prev(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_prev();
+        (self.current, self.index) = (cursor.current, cursor.index);
+    }
+
+    /// Returns the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: see the invariant on `DoublyLinkedList`, the list and the
+        // cursor are borrowed mutably
+        self.current
+            .as_ref()
+            .map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns the next element, the front at the ghost position.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match &self.current {
+            Some(node) => node.borrow().next.clone(),
+            None => self.list.first.clone(),
+        };
+        // SAFETY: as in `current`
+        next.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns the previous element, the back at the ghost position.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match &self.current {
+            Some(node) => node.borrow().prev.clone(),
+            None => self.list.last.clone(),
+        };
+        // SAFETY: as in `current`
+        prev.and_then(|node| node.upgrade())
+            .map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Inserts an element after the current one, or at the front at the ghost position.
+    pub fn insert_after(&mut self, data: T) {
+        let Some(current) = &self.current else {
+            self.list.push_front(data);
+            self.index += 1;
+            return;
+        };
+        let next = current.borrow_mut().next.take();
+        let node = Rc::new(RefCell::new(ListNode {
+            data,
+            next: next.clone(),
+            prev: Some(Rc::downgrade(current)),
+        }));
+        match next {
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&node)),
+            None => self.list.last = Some(Rc::downgrade(&node)),
+        }
+        current.borrow_mut().next = Some(node);
+        self.list.len += 1;
+    }
+
+    /// Inserts an element before the current one, or at the back at the ghost position.
+    pub fn insert_before(&mut self, data: T) {
+        let Some(current) = &self.current else {
+            self.list.push_back(data);
+            self.index += 1;
+            return;
+        };
+        let prev = current.borrow_mut().prev.take();
+        let node = Rc::new(RefCell::new(ListNode {
+            data,
+            next: Some(current.clone()),
+            prev: prev.clone(),
+        }));
+        current.borrow_mut().prev = Some(Rc::downgrade(&node));
+        match prev.and_then(|prev| prev.upgrade()) {
+            Some(prev) => prev.borrow_mut().next = Some(node),
+            None => self.list.first = Some(node),
+        }
+        self.list.len += 1;
+        self.index += 1;
+    }
+
+    /// Removes the current element and returns it, moving to the next one.
+    /// Returns `None` and does nothing at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current.take()?;
+        self.current = current.borrow().next.clone();
+        self.list.unlink(&current);
+        let node = Rc::try_unwrap(current)
+            .ok()
+            .expect("CursorMut::remove_current(): Rc has more than one strong reference")
+            .into_inner();
+        Some(node.data)
+    }
+
+    /// Splits the list after the current element, and returns the elements after it.
+    /// At the ghost position, returns the whole list.
+    pub fn split_after(&mut self) -> DoublyLinkedList<T> {
+        let Some(current) = &self.current else {
+            self.index = 0;
+            return std::mem::replace(self.list, DoublyLinkedList::new());
+        };
+        let Some(next) = current.borrow_mut().next.take() else {
+            return DoublyLinkedList::new();
+        };
+        next.borrow_mut().prev = None;
+        let split = DoublyLinkedList {
+            first: Some(next),
+            last: self.list.last.replace(Rc::downgrade(current)),
+            len: self.list.len - self.index - 1,
+        };
+        self.list.len = self.index + 1;
+        split
+    }
+
+    /// Splits the list before the current element, and returns the elements before it.
+    /// At the ghost position, returns the whole list.
+    pub fn split_before(&mut self) -> DoublyLinkedList<T> {
+        let Some(current) = &self.current else {
+            self.index = 0;
+            return std::mem::replace(self.list, DoublyLinkedList::new());
+        };
+        let Some(prev) = current.borrow_mut().prev.take() else {
+            return DoublyLinkedList::new();
+        };
+        if let Some(prev) = prev.upgrade() {
+            prev.borrow_mut().next = None;
+        }
+        let split = DoublyLinkedList {
+            first: self.list.first.replace(current.clone()),
+            last: Some(prev),
+            len: self.index,
+        };
+        self.list.len -= self.index;
+        self.index = 0;
+        split
+    }
+
+    /// Moves the elements of `other` after the current element,
+    /// or to the front at the ghost position.
+    pub fn splice_after(&mut self, mut other: DoublyLinkedList<T>) {
+        let Some((first, last, len)) = other.take_ends() else {
+            return;
+        };
+        let next = match &self.current {
+            Some(current) => {
+                first.borrow_mut().prev = Some(Rc::downgrade(current));
+                current.borrow_mut().next.replace(first)
+            }
+            None => {
+                self.index += len;
+                self.list.first.replace(first)
+            }
+        };
+        match &next {
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&last)),
+            None => self.list.last = Some(Rc::downgrade(&last)),
+        }
+        last.borrow_mut().next = next;
+        self.list.len += len;
+    }
+
+    /// Moves the elements of `other` before the current element,
+    /// or to the back at the ghost position.
+    pub fn splice_before(&mut self, mut other: DoublyLinkedList<T>) {
+        let Some((first, last, len)) = other.take_ends() else {
+            return;
+        };
+        let prev = match &self.current {
+            Some(current) => {
+                last.borrow_mut().next = Some(current.clone());
+                current
+                    .borrow_mut()
+                    .prev
+                    .replace(Rc::downgrade(&last))
+                    .and_then(|prev| prev.upgrade())
+            }
+            None => self
+                .list
+                .last
+                .replace(Rc::downgrade(&last))
+                .and_then(|prev| prev.upgrade()),
+        };
+        match prev {
+            Some(prev) => {
+                first.borrow_mut().prev = Some(Rc::downgrade(&prev));
+                prev.borrow_mut().next = Some(first);
+            }
+            None => self.list.first = Some(first),
+        }
+        self.list.len += len;
+        self.index += len;
+    }
+}
+
+/// Clones the elements into new nodes. The derived `Clone` would share the
+/// nodes between the two lists, so changing one would change the other.
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for DoublyLinkedList<T> {}
+
 /// Display the linked list
 impl<T: Display> Display for DoublyLinkedList<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "DoublyLinkedList(None ⇌")?;
-        let mut node = self.first.clone();
-        while let Some(r) = node {
-            write!(f, " {} ⇌", r.borrow().data)?;
-            node = r.borrow().next.clone();
+        for data in self {
+            write!(f, " {} ⇌", data)?;
         }
         write!(f, " None)")
     }
 }
 
-/// Initializes a linked list and pushes elements into the list
-/// in **reverse order**.
+/// Initializes a doubly linked list with the elements in order, from the front
+/// to the back.
 ///
 /// # Examples
 ///
 /// ```rust
-/// use rs_algorithm_practise::data_structure::doubly_linked_list::DoublyLinkedList;
 /// use rs_algorithm_practise::doubly_linked_list;
 ///
-/// let dll: DoublyLinkedList<i32> = doubly_linked_list![1,2,3];
-/// println!("{}", dll); // DoublyLinkedList(None ⇌ 1 ⇌ 2 ⇌ 3 ⇌ None)
+/// let dll = doubly_linked_list![1, 2, 3];
+/// assert_eq!(dll.to_string(), "DoublyLinkedList(None ⇌ 1 ⇌ 2 ⇌ 3 ⇌ None)");
 /// ```
 #[macro_export]
 macro_rules! doubly_linked_list {
-    ($($elem:expr),*) => {
-        {
-            let mut temp_list = DoublyLinkedList::new();
-            let mut vec = vec![$($elem),*];
-            vec.reverse();
-            for elem in vec {
-                temp_list.push_front(elem);
-            }
-            temp_list
-        }
+    ($($elem:expr),* $(,)?) => {
+        <$crate::data_structure::doubly_linked_list::DoublyLinkedList<_>
+            as ::core::iter::FromIterator<_>>::from_iter([$($elem),*])
     };
 }
 
@@ -317,6 +1213,76 @@ mod tests {
         assert_eq!(dll, doubly_linked_list![]);
     }
 
+    #[test]
+    fn test_sort() {
+        let mut dll = doubly_linked_list![5, 1, 4, 2, 3, 2];
+        dll.sort();
+        assert_eq!(dll, doubly_linked_list![1, 2, 2, 3, 4, 5]);
+        // the prev pointers and the last node are relinked as well
+        assert_eq!(dll.pop_back(), Some(5));
+        dll.push_back(6);
+        assert_eq!(dll, doubly_linked_list![1, 2, 2, 3, 4, 6]);
+
+        let mut dll: DoublyLinkedList<i32> = doubly_linked_list![];
+        dll.sort();
+        assert!(dll.is_empty());
+
+        // sort a longer list, and compare with Vec::sort
+        let mut r = crate::supplementary::random::RandomGen::new(7);
+        let mut v: Vec<usize> = (0..1000).map(|_| r.rand_usize(100)).collect();
+        let mut dll = DoublyLinkedList::new();
+        for x in &v {
+            dll.push_back(*x);
+        }
+        dll.sort();
+        v.sort();
+        let mut sorted = Vec::new();
+        while let Some(x) = dll.pop_back() {
+            sorted.push(x);
+        }
+        sorted.reverse();
+        assert_eq!(v, sorted);
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        let mut dll = doubly_linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        dll.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            dll,
+            doubly_linked_list![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+
+        let mut dll = doubly_linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        dll.insertion_sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            dll,
+            doubly_linked_list![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+    }
+
+    #[test]
+    fn test_insertion_sort() {
+        let mut dll = doubly_linked_list![5, 1, 4, 2, 3];
+        dll.insertion_sort();
+        assert_eq!(dll, doubly_linked_list![1, 2, 3, 4, 5]);
+        assert_eq!(dll.pop_back(), Some(5));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut dll = doubly_linked_list![1, 3, 5];
+        dll.merge(doubly_linked_list![2, 3, 4, 6, 7]);
+        assert_eq!(dll, doubly_linked_list![1, 2, 3, 3, 4, 5, 6, 7]);
+        assert_eq!(dll.pop_back(), Some(7));
+
+        let mut dll = doubly_linked_list![];
+        dll.merge(doubly_linked_list![1, 2]);
+        assert_eq!(dll, doubly_linked_list![1, 2]);
+        dll.merge(doubly_linked_list![]);
+        assert_eq!(dll, doubly_linked_list![1, 2]);
+    }
+
     #[test]
     fn test_pop_empty() {
         let mut dll: DoublyLinkedList<i32> = DoublyLinkedList::new();
@@ -325,4 +1291,210 @@ mod tests {
         let b = dll.pop_back();
         assert_eq!(b, None);
     }
+
+    #[test]
+    fn test_len_and_iter() {
+        let mut dll: DoublyLinkedList<i32> = (1..=4).collect();
+        assert_eq!(dll.len(), 4);
+        dll.push_front(0);
+        dll.pop_back();
+        assert_eq!(dll.len(), 4);
+        assert_eq!(dll.front(), Some(&0));
+        if let Some(front) = dll.front_mut() {
+            *front = 10;
+        }
+        for x in &mut dll {
+            *x += 1;
+        }
+        assert_eq!(dll.iter().len(), 4);
+        assert_eq!(dll.iter().copied().collect::<Vec<_>>(), vec![11, 2, 3, 4]);
+        assert!(dll.contains(&3));
+        assert!(!dll.contains(&10));
+
+        dll.extend([5, 6]);
+        dll.sort_by(|a, b| b.cmp(a));
+        assert_eq!(dll.len(), 6);
+        assert_eq!(dll.into_iter().collect::<Vec<_>>(), vec![11, 6, 5, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_double_ended_iter() {
+        let mut dll: DoublyLinkedList<i32> = (1..=5).collect();
+        assert_eq!((dll.front(), dll.back()), (Some(&1), Some(&5)));
+        *dll.back_mut().unwrap() = 50;
+        assert_eq!(
+            dll.iter().rev().copied().collect::<Vec<_>>(),
+            vec![50, 4, 3, 2, 1]
+        );
+
+        // the two ends stop where they meet
+        let mut iter = dll.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&1), Some(&50)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!((iter.next_back(), iter.next()), (Some(&4), Some(&2)));
+        assert_eq!((iter.next(), iter.next_back()), (Some(&3), None));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = dll.iter_mut();
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            std::mem::swap(front, back);
+        }
+        assert_eq!(dll, doubly_linked_list![50, 4, 3, 2, 1]);
+
+        let mut iter = dll.into_iter();
+        assert_eq!((iter.next_back(), iter.next()), (Some(1), Some(50)));
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let mut empty: DoublyLinkedList<i32> = doubly_linked_list![];
+        assert_eq!(empty.back(), None);
+        assert_eq!(empty.back_mut(), None);
+        assert_eq!(empty.iter().next_back(), None);
+    }
+
+    #[test]
+    fn test_retain_and_clear() {
+        let mut dll: DoublyLinkedList<i32> = (1..=6).collect();
+        dll.retain(|x| x % 3 != 0);
+        assert_eq!(dll, doubly_linked_list![1, 2, 4, 5]);
+        assert_eq!(dll.len(), 4);
+        // the last node was removed, so the back must be updated
+        dll.retain(|&x| x < 5);
+        dll.push_back(7);
+        assert_eq!(dll, doubly_linked_list![1, 2, 4, 7]);
+        dll.retain(|&x| x > 1);
+        assert_eq!(dll.pop_front(), Some(2));
+
+        dll.clear();
+        assert!(dll.is_empty());
+        assert_eq!(dll.len(), 0);
+        assert_eq!(dll.front(), None);
+    }
+
+    #[test]
+    fn test_clone_is_deep() {
+        let dll = doubly_linked_list![1, 2];
+        let mut cloned = dll.clone();
+        cloned.push_back(3);
+        *cloned.front_mut().unwrap() = 0;
+        assert_eq!(dll, doubly_linked_list![1, 2]);
+        assert_eq!(cloned, doubly_linked_list![0, 2, 3]);
+    }
+
+    // check the links in both directions and the length
+    fn assert_links<T: PartialEq + Clone + std::fmt::Debug>(
+        dll: &DoublyLinkedList<T>,
+        expected: &[T],
+    ) {
+        assert_eq!(dll.len(), expected.len());
+        assert_eq!(dll.iter().cloned().collect::<Vec<_>>(), expected);
+        let mut backward = Vec::new();
+        let mut cursor = dll.cursor_back();
+        while let Some(x) = cursor.current() {
+            assert_eq!(cursor.index(), Some(expected.len() - 1 - backward.len()));
+            backward.push(x.clone());
+            cursor.move_prev();
+        }
+        backward.reverse();
+        assert_eq!(backward, expected);
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let dll = doubly_linked_list![1, 2, 3];
+        let mut cursor = dll.cursor_front();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        assert_eq!(
+            (cursor.peek_prev(), cursor.peek_next()),
+            (Some(&1), Some(&3))
+        );
+        cursor.move_next();
+        cursor.move_next();
+        // the ghost position between the back and the front
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(
+            (cursor.peek_prev(), cursor.peek_next()),
+            (Some(&3), Some(&1))
+        );
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+
+        let empty: DoublyLinkedList<i32> = doubly_linked_list![];
+        let mut cursor = empty.cursor_back();
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+    }
+
+    #[test]
+    fn test_cursor_insert_and_remove() {
+        let mut dll = doubly_linked_list![2, 4];
+        let mut cursor = dll.cursor_front_mut();
+        cursor.insert_before(1);
+        cursor.insert_after(3);
+        assert_eq!(cursor.index(), Some(1));
+        *cursor.current().unwrap() *= 10;
+        cursor.move_prev();
+        cursor.move_prev();
+        // at the ghost position, insert at the ends
+        cursor.insert_after(0);
+        cursor.insert_before(5);
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_links(&dll, &[0, 1, 20, 3, 4, 5]);
+
+        let mut cursor = dll.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(0));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(20));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_links(&dll, &[1, 3, 4]);
+        dll.push_back(6);
+        assert_links(&dll, &[1, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_cursor_split_and_splice() {
+        let mut dll: DoublyLinkedList<i32> = (0..6).collect();
+        let mut cursor = dll.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_links(&before, &[0, 1]);
+        assert_links(&after, &[3, 4, 5]);
+        assert_links(&dll, &[2]);
+
+        let mut cursor = dll.cursor_front_mut();
+        cursor.splice_after(after);
+        cursor.splice_before(before);
+        assert_eq!(cursor.index(), Some(2));
+        cursor.splice_before(doubly_linked_list![]);
+        assert_links(&dll, &[0, 1, 2, 3, 4, 5]);
+
+        // at the ghost position, splice at the ends and split off everything
+        let mut cursor = dll.cursor_back_mut();
+        cursor.move_next();
+        cursor.splice_after(doubly_linked_list![-1]);
+        cursor.splice_before(doubly_linked_list![6, 7]);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(8));
+        assert_links(&dll, &[-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let mut cursor = dll.cursor_front_mut();
+        cursor.move_prev();
+        let all = cursor.split_after();
+        assert!(cursor.split_before().is_empty());
+        assert!(dll.is_empty());
+        assert_links(&all, &[-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+    }
 }
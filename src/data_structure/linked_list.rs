@@ -1,9 +1,34 @@
-use core::panic;
-use std::fmt::Display;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// the error of an operation on an index past the end of a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfBounds {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "index {} is out of bounds for a list of length {}",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for IndexOutOfBounds {}
 
 /// a single linked list, T must implement `Display` trait.
 /// this linked list will be displayed as `a -> b -> c -> ... -> None`
-#[derive(Default, Debug, Clone, PartialEq)]
+///
+/// every node owns the rest of the list, so the derived `Drop`, `Clone`,
+/// `PartialEq` and `Debug` would recurse once per node and overflow the stack
+/// on long lists. they are implemented with loops instead.
+#[derive(Default)]
 pub struct LinkedList<T>(Option<(T, Box<LinkedList<T>>)>);
 
 impl<T> LinkedList<T> {
@@ -50,8 +75,8 @@ impl<T> LinkedList<T> {
     #[inline]
     pub fn pop_front(&mut self) -> Option<T> {
         match self.0.take() {
-            Some((data, child)) => {
-                self.0 = child.0;
+            Some((data, mut child)) => {
+                self.0 = child.0.take();
                 Some(data)
             }
             None => None,
@@ -65,10 +90,39 @@ impl<T> LinkedList<T> {
     /// `data:T` data to be inserted
     #[inline]
     pub fn push_back(&mut self, data: T) {
-        match self.0 {
-            Some((_, ref mut child)) => child.push_back(data),
-            None => self.push_front(data),
+        self.tail_mut().push_front(data);
+    }
+
+    /// delete the last element in the list and return it,
+    /// this function need to iterate over the linked list.
+    ///
+    /// # Returns:
+    /// `Option<T>`: return the deleted element,
+    /// if the list is empty, return a `None`
+    pub fn pop_back(&mut self) -> Option<T> {
+        let mut node = self;
+        while node.0.as_ref().is_some_and(|(_, child)| !child.is_empty()) {
+            node = &mut node.0.as_mut().unwrap().1;
         }
+        node.pop_front()
+    }
+
+    /// the number of elements, this function need to iterate over the linked list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// the element at a given index, or `None` if the index is out of bound
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// the mutable element at a given index, or `None` if the index is out of bound
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
     }
 
     /// insert an element at a given index
@@ -76,41 +130,503 @@ impl<T> LinkedList<T> {
     /// # Arguments
     /// `element: T`: the element to be inserted.
     ///
-    /// `index: usize`: insert the element at this index.
+    /// `index: usize`: insert the element at this index, at most the length of the list.
+    ///
+    /// # Returns
+    /// `Err(IndexOutOfBounds)` if the index is greater than the length of the list,
+    /// the list is not changed.
     ///
-    /// # Panics
-    /// panic if the index is out of bound
-    pub fn insert(&mut self, data: T, index: usize) {
-        if index == 0 {
-            self.push_front(data);
-            return;
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::{IndexOutOfBounds, LinkedList};
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// let mut ll = linked_list![1, 3];
+    /// assert_eq!(ll.insert(2, 1), Ok(()));
+    /// assert_eq!(ll.insert(4, 3), Ok(()));
+    /// assert_eq!(ll.insert(6, 5), Err(IndexOutOfBounds { index: 5, len: 4 }));
+    /// assert_eq!(ll, linked_list![1, 2, 3, 4]);
+    /// ```
+    pub fn insert(&mut self, data: T, index: usize) -> Result<(), IndexOutOfBounds> {
+        self.sublist_mut(index)?.push_front(data);
+        Ok(())
+    }
+
+    /// delete the element at a given index and return it
+    ///
+    /// # Returns
+    /// `Err(IndexOutOfBounds)` if the index is not less than the length of the list,
+    /// the list is not changed.
+    pub fn remove(&mut self, index: usize) -> Result<T, IndexOutOfBounds> {
+        self.sublist_mut(index)?
+            .pop_front()
+            .ok_or(IndexOutOfBounds { index, len: index })
+    }
+
+    // the list that starts at a given index, which is the empty list
+    // at the end if the index is the length of the list
+    fn sublist_mut(&mut self, index: usize) -> Result<&mut LinkedList<T>, IndexOutOfBounds> {
+        let mut node = self;
+        for len in 0..index {
+            match node.0 {
+                Some((_, ref mut child)) => node = child,
+                None => return Err(IndexOutOfBounds { index, len }),
+            }
         }
+        Ok(node)
+    }
 
+    // detach the first node and return it as a single-element list.
+    // the node keeps its (empty) tail box, so nothing is allocated.
+    fn split_first_node(&mut self) -> Option<LinkedList<T>> {
+        let rest = match self.0.as_mut() {
+            Some((_, child)) => std::mem::replace(&mut **child, LinkedList(None)),
+            None => return None,
+        };
+        Some(std::mem::replace(self, rest))
+    }
+
+    /// sort the list in place with a bottom-up merge sort.
+    /// the sort is stable, takes O(n log n) time, and relinks the existing
+    /// nodes instead of allocating new ones.
+    ///
+    /// # Arguments:
+    /// `compare: F` a function that returns the ordering of two elements
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::LinkedList;
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// let mut ll = linked_list![1, 3, 2];
+    /// ll.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(ll, linked_list![3, 2, 1]);
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // runs[i] is either empty or a sorted run of 2^i elements,
+        // and runs in higher slots hold earlier elements
+        let mut runs: [LinkedList<T>; usize::BITS as usize] =
+            std::array::from_fn(|_| LinkedList::new());
+
+        while let Some(mut carry) = self.split_first_node() {
+            let mut i = 0;
+            while !runs[i].is_empty() {
+                let run = std::mem::replace(&mut runs[i], LinkedList::new());
+                carry = merge_lists(run, carry, &mut compare);
+                i += 1;
+            }
+            runs[i] = carry;
+        }
+
+        for run in runs {
+            let rest = std::mem::replace(self, LinkedList::new());
+            *self = merge_lists(run, rest, &mut compare);
+        }
+    }
+
+    /// sort the list in place with insertion sort.
+    /// the sort is stable and takes O(n^2) time, which is fine for short lists.
+    ///
+    /// # Arguments:
+    /// `compare: F` a function that returns the ordering of two elements
+    pub fn insertion_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut sorted = LinkedList::new();
+        while let Some(node) = self.split_first_node() {
+            let data = node.peak().unwrap();
+            // find the first element that is greater than the new one
+            let mut cursor = &mut sorted;
+            while let Some((ref value, _)) = cursor.0 {
+                if compare(value, data) == Ordering::Greater {
+                    break;
+                }
+                cursor = &mut cursor.0.as_mut().unwrap().1;
+            }
+            let rest = std::mem::replace(cursor, node);
+            *cursor.0.as_mut().unwrap().1 = rest;
+        }
+        *self = sorted;
+    }
+
+    /// merge another sorted list into this sorted list, keeping it sorted.
+    /// on equal elements, the elements of this list come first.
+    ///
+    /// # Arguments:
+    /// `other: LinkedList<T>` a list sorted by the same `compare` function
+    ///
+    /// `compare: F` a function that returns the ordering of two elements
+    pub fn merge_by<F>(&mut self, other: LinkedList<T>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let this = std::mem::replace(self, LinkedList::new());
+        *self = merge_lists(this, other, &mut compare);
+    }
+
+    /// iterate over the elements from the head to the tail
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::LinkedList;
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// let ll = linked_list![1, 2, 3];
+    /// let doubled: LinkedList<i32> = ll.iter().map(|x| x * 2).collect();
+    /// assert_eq!(doubled, linked_list![2, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { node: self }
+    }
+
+    /// iterate over mutable references to the elements from the head to the tail
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { node: Some(self) }
+    }
+
+    // the empty list at the end of this list
+    fn tail_mut(&mut self) -> &mut LinkedList<T> {
         let mut node = self;
+        while let Some((_, ref mut child)) = node.0 {
+            node = child;
+        }
+        node
+    }
 
-        for _ in 0..index - 1_usize {
-            match node.0.as_mut() {
-                Some((_, child)) => {
-                    node = child;
+    /// reverse the list in place, by relinking the nodes
+    pub fn reverse(&mut self) {
+        let mut reversed = LinkedList::new();
+        while let Some(mut node) = self.split_first_node() {
+            // the detached node has an empty tail, put the reversed list there
+            std::mem::swap(&mut *node.0.as_mut().unwrap().1, &mut reversed);
+            reversed = node;
+        }
+        *self = reversed;
+    }
+
+    /// split the list into two at the given index, and return the elements
+    /// from the index on. this list keeps the elements before the index.
+    ///
+    /// # Returns
+    /// `Err(IndexOutOfBounds)` if the index is greater than the length of the list,
+    /// the list is not changed.
+    pub fn split_off(&mut self, index: usize) -> Result<LinkedList<T>, IndexOutOfBounds> {
+        let rest = self.sublist_mut(index)?;
+        Ok(std::mem::replace(rest, LinkedList::new()))
+    }
+
+    /// move all the elements of `other` to the end of this list, leaving `other` empty.
+    /// this function need to iterate over this list, but not over `other`.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        *self.tail_mut() = std::mem::replace(other, LinkedList::new());
+    }
+
+    /// remove the consecutive elements for which `same_bucket` returns true,
+    /// but the first one.
+    ///
+    /// # Arguments:
+    /// `same_bucket: F` is called with an element and the last kept element before it
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut node = self;
+        while let Some((ref mut kept, ref mut child)) = node.0 {
+            while let Some((ref mut value, _)) = child.0 {
+                if !same_bucket(value, kept) {
+                    break;
                 }
-                None => {
-                    panic!("index out of bound");
+                child.pop_front();
+            }
+            node = child;
+        }
+    }
+
+    /// keep only the elements for which `f` returns true, in the same order
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut node = self;
+        while let Some((ref value, _)) = node.0 {
+            if f(value) {
+                node = &mut node.0.as_mut().unwrap().1;
+            } else {
+                node.pop_front();
+            }
+        }
+    }
+
+    /// the middle element, found in one pass with a slow and a fast pointer.
+    /// for an even length, it is the second of the two middle elements.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::LinkedList;
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// assert_eq!(linked_list![1, 2, 3].find_middle(), Some(&2));
+    /// assert_eq!(linked_list![1, 2, 3, 4].find_middle(), Some(&3));
+    /// assert_eq!(LinkedList::<i32>::new().find_middle(), None);
+    /// ```
+    pub fn find_middle(&self) -> Option<&T> {
+        let mut slow = self;
+        let mut fast = self;
+        // the fast pointer moves two nodes for every node of the slow pointer
+        while let Some((_, ref next)) = fast.0 {
+            match next.0 {
+                Some((_, ref after_next)) => {
+                    fast = after_next;
+                    slow = &slow.0.as_ref().unwrap().1;
                 }
+                None => break,
             }
         }
+        slow.peak()
+    }
+
+    /// the n-th element from the end, `0` being the last one, found in one pass
+    /// with a pointer that leads by `n + 1` nodes. returns `None` if the list
+    /// has at most `n` elements.
+    pub fn nth_from_end(&self, n: usize) -> Option<&T> {
+        let mut lead = self.iter();
+        for _ in 0..=n {
+            lead.next()?;
+        }
+        let mut trail = self.iter();
+        for _ in lead {
+            trail.next();
+        }
+        trail.next()
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+    /// whether the list contains an element equal to `value`
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|data| data == value)
+    }
+
+    /// remove the consecutive repeated elements, see [`LinkedList::dedup_by`]
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::LinkedList;
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// let mut ll = linked_list![1, 1, 2, 3, 3, 3, 1];
+    /// ll.dedup();
+    /// assert_eq!(ll, linked_list![1, 2, 3, 1]);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+impl<T: Ord> LinkedList<T> {
+    /// sort the list in ascending order, see [`LinkedList::sort_by`]
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp)
+    }
+
+    /// sort the list in ascending order, see [`LinkedList::insertion_sort_by`]
+    #[inline]
+    pub fn insertion_sort(&mut self) {
+        self.insertion_sort_by(T::cmp)
+    }
+
+    /// merge another list sorted in ascending order into this one,
+    /// see [`LinkedList::merge_by`]
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::data_structure::linked_list::LinkedList;
+    /// use rs_algorithm_practise::linked_list;
+    ///
+    /// let mut ll = linked_list![1, 4, 5];
+    /// ll.merge(linked_list![2, 3, 6]);
+    /// assert_eq!(ll, linked_list![1, 2, 3, 4, 5, 6]);
+    /// ```
+    #[inline]
+    pub fn merge(&mut self, other: LinkedList<T>) {
+        self.merge_by(other, T::cmp)
+    }
+}
 
-        node.insert_next(data);
+// merge two sorted lists by relinking their nodes,
+// on equal elements the elements of `a` come first
+fn merge_lists<T, F>(mut a: LinkedList<T>, mut b: LinkedList<T>, compare: &mut F) -> LinkedList<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut res = LinkedList::new();
+    let mut tail = &mut res;
+    loop {
+        let source = match (a.peak(), b.peak()) {
+            (Some(x), Some(y)) => match compare(y, x) {
+                Ordering::Less => &mut b,
+                _ => &mut a,
+            },
+            (Some(_), None) => {
+                *tail = a;
+                return res;
+            }
+            (None, _) => {
+                *tail = b;
+                return res;
+            }
+        };
+        // move the first node of `source` to the tail of the result
+        *tail = std::mem::replace(source, LinkedList(None));
+        let child = &mut tail.0.as_mut().unwrap().1;
+        *source = std::mem::replace(&mut **child, LinkedList(None));
+        tail = &mut **child;
     }
+}
+
+/// iterator over the elements of a [`LinkedList`], from the head to the tail
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    node: &'a LinkedList<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
 
-    // insert a node with value `data: T` after this node
-    fn insert_next(&mut self, data: T) {
-        // if the list is None, panic
-        if self.0.is_none() {
-            panic!("index out of bound");
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (data, child) = self.node.0.as_ref()?;
+        self.node = child;
+        Some(data)
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { node: self.node }
+    }
+}
+
+/// iterator over mutable references to the elements of a [`LinkedList`],
+/// from the head to the tail
+#[derive(Debug)]
+pub struct IterMut<'a, T> {
+    node: Option<&'a mut LinkedList<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (data, child) = self.node.take()?.0.as_mut()?;
+        self.node = Some(child);
+        Some(data)
+    }
+}
+
+/// owning iterator over the elements of a [`LinkedList`], from the head to the tail
+#[derive(Debug, Clone)]
+pub struct IntoIter<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// build a list with the elements in the order of the iterator
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// append the elements at the end of the list, in the order of the iterator
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut tail = self.tail_mut();
+        for data in iter {
+            *tail = LinkedList(Some((data, Box::new(LinkedList(None)))));
+            tail = &mut tail.0.as_mut().unwrap().1;
         }
-        let (self_value, child) = self.0.take().unwrap();
+    }
+}
 
-        self.0 = Some((self_value, Box::new(LinkedList(Some((data, child))))));
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        // detach the nodes one by one, so each is dropped with an empty tail
+        let mut next = self.0.take();
+        while let Some((_, mut child)) = next {
+            next = child.0.take();
+        }
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+/// debug the list as a list of its elements, e.g. `[1, 2, 3]`
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
@@ -126,8 +642,220 @@ impl<T: Display> Display for LinkedList<T> {
     }
 }
 
-/// init a linked list, and push the items into the list
-/// in a reverse order.
+/// a cycle in a sequence of nodes, found by [`find_cycle_floyd`] or [`find_cycle_brent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle<N> {
+    /// the first node of the cycle
+    pub entry: N,
+    /// the number of steps from the head to the entry
+    pub start: usize,
+    /// the number of nodes in the cycle
+    pub len: usize,
+}
+
+/// find a cycle with Floyd's tortoise and hare, in O(start + len) steps and O(1) memory.
+///
+/// the hare moves two nodes for every node of the tortoise, so if there is a
+/// cycle they meet in it. the meeting point is then as far from the entry as
+/// the head is, so a pointer from the head and one from the meeting point,
+/// moving one node at a time, meet at the entry.
+///
+/// # Arguments:
+/// `head: N` the first node
+///
+/// `next: F` the node after a node, or `None` at the end of the sequence
+pub fn find_cycle_floyd<N, F>(head: N, next: F) -> Option<Cycle<N>>
+where
+    N: Copy + PartialEq,
+    F: Fn(N) -> Option<N>,
+{
+    let mut tortoise = head;
+    let mut hare = head;
+    loop {
+        tortoise = next(tortoise)?;
+        hare = next(next(hare)?)?;
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    let mut entry = head;
+    let mut start = 0;
+    while entry != hare {
+        entry = next(entry)?;
+        hare = next(hare)?;
+        start += 1;
+    }
+
+    let mut len = 1;
+    let mut node = next(entry)?;
+    while node != entry {
+        node = next(node)?;
+        len += 1;
+    }
+    Some(Cycle { entry, start, len })
+}
+
+/// find a cycle with Brent's algorithm, in O(start + len) steps and O(1) memory.
+///
+/// the hare moves one node at a time, and the tortoise jumps to the hare after
+/// 1, 2, 4, ... steps, so once both are in the cycle and the power of two is at
+/// least its length, the hare comes back to the tortoise, which gives the length
+/// of the cycle. it calls `next` fewer times than [`find_cycle_floyd`].
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::linked_list::{find_cycle_brent, Cycle};
+///
+/// // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+/// let next = |node: usize| Some(if node == 4 { 2 } else { node + 1 });
+/// let cycle = find_cycle_brent(0, next);
+/// assert_eq!(cycle, Some(Cycle { entry: 2, start: 2, len: 3 }));
+/// ```
+pub fn find_cycle_brent<N, F>(head: N, next: F) -> Option<Cycle<N>>
+where
+    N: Copy + PartialEq,
+    F: Fn(N) -> Option<N>,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = head;
+    let mut hare = next(head)?;
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare;
+            power *= 2;
+            len = 0;
+        }
+        hare = next(hare)?;
+        len += 1;
+    }
+
+    // move a pointer `len` nodes ahead, then both meet at the entry
+    let mut entry = head;
+    let mut hare = head;
+    for _ in 0..len {
+        hare = next(hare)?;
+    }
+    let mut start = 0;
+    while entry != hare {
+        entry = next(entry)?;
+        hare = next(hare)?;
+        start += 1;
+    }
+    Some(Cycle { entry, start, len })
+}
+
+/// a handle to a node of a [`NodeArena`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    next: Option<NodeId>,
+}
+
+/// singly linked nodes that may form cycles.
+///
+/// a [`LinkedList`] owns its nodes, so it can never have a cycle. the nodes of
+/// an arena are stored in a `Vec` and link to each other by index instead, so
+/// any node can link to any other node, like the pointers of a list in C.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::linked_list::NodeArena;
+///
+/// let mut arena = NodeArena::new();
+/// let head = arena.insert_list(1..=5).unwrap();
+/// assert_eq!(arena.find_cycle(head), None);
+///
+/// // link the last node back to the second one
+/// let second = arena.next(head).unwrap();
+/// let mut last = head;
+/// while let Some(next) = arena.next(last) {
+///     last = next;
+/// }
+/// arena.link(last, Some(second));
+/// let cycle = arena.find_cycle(head).unwrap();
+/// assert_eq!((arena[cycle.entry], cycle.start, cycle.len), (2, 1, 4));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NodeArena<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> NodeArena<T> {
+    #[inline]
+    pub fn new() -> Self {
+        NodeArena { nodes: Vec::new() }
+    }
+
+    /// add a node that links to nothing, and return its id
+    pub fn insert(&mut self, value: T) -> NodeId {
+        self.nodes.push(Node { value, next: None });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// add a node for every value, each linking to the next one,
+    /// and return the id of the first node, or `None` if there are no values
+    pub fn insert_list<I: IntoIterator<Item = T>>(&mut self, values: I) -> Option<NodeId> {
+        let first = self.nodes.len();
+        for value in values {
+            let id = self.insert(value);
+            if id.0 > first {
+                self.link(NodeId(id.0 - 1), Some(id));
+            }
+        }
+        (self.nodes.len() > first).then_some(NodeId(first))
+    }
+
+    /// make `node` link to `next`
+    #[inline]
+    pub fn link(&mut self, node: NodeId, next: Option<NodeId>) {
+        self.nodes[node.0].next = next;
+    }
+
+    /// the node that `node` links to
+    #[inline]
+    pub fn next(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes[node.0].next
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// the cycle reached by following the links from `head`, see [`find_cycle_brent`]
+    #[inline]
+    pub fn find_cycle(&self, head: NodeId) -> Option<Cycle<NodeId>> {
+        find_cycle_brent(head, |node| self.next(node))
+    }
+}
+
+impl<T> Index<NodeId> for NodeArena<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, node: NodeId) -> &T {
+        &self.nodes[node.0].value
+    }
+}
+
+impl<T> IndexMut<NodeId> for NodeArena<T> {
+    #[inline]
+    fn index_mut(&mut self, node: NodeId) -> &mut T {
+        &mut self.nodes[node.0].value
+    }
+}
+
+/// init a linked list with the items, in the same order
 ///
 /// # Examples
 /// ```rust
@@ -139,16 +867,10 @@ impl<T: Display> Display for LinkedList<T> {
 /// ```
 #[macro_export]
 macro_rules! linked_list {
-    ($($elem:expr),*) => {
-        {
-            let mut temp_list = LinkedList::new();
-            let mut vec = vec![$($elem),*];
-            vec.reverse();
-            for elem in vec {
-                temp_list.push_front(elem);
-            }
-            temp_list
-        }
+    ($($elem:expr),* $(,)?) => {
+        <$crate::data_structure::linked_list::LinkedList<_> as ::core::iter::FromIterator<_>>::from_iter(
+            [$($elem),*],
+        )
     };
 }
 
@@ -180,22 +902,118 @@ mod tests {
         ll.push_front(2);
         ll.push_front(1);
 
-        ll.insert(0, 0);
-        println!("{}", ll);
-        ll.insert(5, 5);
-        println!("{}", ll);
+        assert_eq!(ll.insert(0, 0), Ok(()));
+        assert_eq!(ll.insert(5, 5), Ok(()));
+        assert_eq!(ll, linked_list![0, 1, 2, 3, 4, 5]);
+        assert_eq!(ll.insert(7, 7), Err(IndexOutOfBounds { index: 7, len: 6 }));
+        assert_eq!(ll.len(), 6);
     }
 
     #[test]
     fn test_insert_internal() {
+        let mut ll = linked_list![1, 2, 3, 4];
+        assert_eq!(ll.insert(0, 1), Ok(()));
+        assert_eq!(ll, linked_list![1, 0, 2, 3, 4]);
+
+        let mut empty = LinkedList::new();
+        assert_eq!(
+            empty.insert(0, 1),
+            Err(IndexOutOfBounds { index: 1, len: 0 })
+        );
+        assert_eq!(empty.insert(0, 0), Ok(()));
+        assert_eq!(empty, linked_list![0]);
+    }
+
+    #[test]
+    fn test_indexed_access() {
+        let mut ll = linked_list![10, 20, 30, 40];
+        assert_eq!(ll.len(), 4);
+        assert_eq!(ll.get(1), Some(&20));
+        assert_eq!(ll.get(4), None);
+        *ll.get_mut(3).unwrap() += 1;
+        assert_eq!(ll.get_mut(9), None);
+
+        assert_eq!(ll.remove(1), Ok(20));
+        assert_eq!(ll.remove(3), Err(IndexOutOfBounds { index: 3, len: 3 }));
+        assert_eq!(ll.remove(0), Ok(10));
+        assert!(ll.contains(&41));
+        assert!(!ll.contains(&40));
+
+        assert_eq!(ll.pop_back(), Some(41));
+        assert_eq!(ll.pop_back(), Some(30));
+        assert_eq!(ll.pop_back(), None);
+        assert_eq!(ll.len(), 0);
+        assert_eq!(
+            IndexOutOfBounds { index: 3, len: 2 }.to_string(),
+            "index 3 is out of bounds for a list of length 2"
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut ll = linked_list![5, 1, 4, 2, 3, 2];
+        ll.sort();
+        assert_eq!(ll, linked_list![1, 2, 2, 3, 4, 5]);
+
+        let mut ll: LinkedList<i32> = linked_list![];
+        ll.sort();
+        assert!(ll.is_empty());
+
+        // sort a longer list, and compare with Vec::sort
+        let mut r = crate::supplementary::random::RandomGen::new(7);
+        let mut v: Vec<usize> = (0..1000).map(|_| r.rand_usize(100)).collect();
         let mut ll = LinkedList::new();
-        ll.push_front(4);
-        ll.push_front(3);
-        ll.push_front(2);
-        ll.push_front(1);
+        for x in v.iter().rev() {
+            ll.push_front(*x);
+        }
+        ll.sort();
+        v.sort();
+        let mut sorted = Vec::new();
+        while let Some(x) = ll.pop_front() {
+            sorted.push(x);
+        }
+        assert_eq!(v, sorted);
+    }
 
-        ll.insert_next(0);
-        println!("{}", ll);
+    #[test]
+    fn test_sort_is_stable() {
+        let mut ll = linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        ll.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            ll,
+            linked_list![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+
+        let mut ll = linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        ll.insertion_sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            ll,
+            linked_list![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+    }
+
+    #[test]
+    fn test_insertion_sort() {
+        let mut ll = linked_list![5, 1, 4, 2, 3];
+        ll.insertion_sort();
+        assert_eq!(ll, linked_list![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut ll = linked_list![1, 3, 5];
+        ll.merge(linked_list![2, 3, 4, 6, 7]);
+        assert_eq!(ll, linked_list![1, 2, 3, 3, 4, 5, 6, 7]);
+
+        let mut ll = linked_list![];
+        ll.merge(linked_list![1, 2]);
+        assert_eq!(ll, linked_list![1, 2]);
+        ll.merge(linked_list![]);
+        assert_eq!(ll, linked_list![1, 2]);
+
+        let mut ll = linked_list![(1, 'a'), (2, 'a')];
+        ll.merge_by(linked_list![(1, 'b'), (2, 'b')], |a, b| a.0.cmp(&b.0));
+        assert_eq!(ll, linked_list![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
     }
 
     #[test]
@@ -203,4 +1021,164 @@ mod tests {
         let ll: LinkedList<i32> = linked_list![1, 2, 3];
         println!("{}", ll);
     }
+
+    #[test]
+    fn test_iter() {
+        let mut ll = linked_list![1, 2, 3];
+        assert_eq!(ll.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        for x in ll.iter_mut() {
+            *x *= 10;
+        }
+        for x in &mut ll {
+            *x += 1;
+        }
+        assert_eq!((&ll).into_iter().sum::<i32>(), 63);
+        assert_eq!(ll.into_iter().collect::<Vec<_>>(), vec![11, 21, 31]);
+
+        let empty: LinkedList<i32> = linked_list![];
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn test_from_iter_and_extend() {
+        let mut ll: LinkedList<usize> = (0..5).collect();
+        assert_eq!(ll, linked_list![0, 1, 2, 3, 4]);
+        ll.extend(vec![5, 6]);
+        ll.extend(std::iter::empty());
+        assert_eq!(
+            ll.iter().copied().collect::<Vec<_>>(),
+            (0..7).collect::<Vec<_>>()
+        );
+
+        let mut ll = LinkedList::new();
+        ll.extend("ab".chars());
+        assert_eq!(ll, linked_list!['a', 'b',]);
+    }
+
+    #[test]
+    fn test_long_list() {
+        const N: usize = 1_000_000;
+        let mut ll: LinkedList<usize> = (0..N).collect();
+        ll.push_back(N);
+        ll.push_back(N + 1);
+        assert_eq!(ll.len(), N + 2);
+
+        let copy = ll.clone();
+        assert_eq!(ll, copy);
+        assert_eq!(copy.get(N + 1), Some(&(N + 1)));
+        ll.sort_by(|a, b| b.cmp(a));
+        assert_eq!(ll.peak(), Some(&(N + 1)));
+        assert_ne!(ll, copy);
+        // both lists are dropped here without recursion
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", linked_list![1, 2, 3]), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+    }
+
+    #[test]
+    fn test_reverse_split_append() {
+        let mut ll: LinkedList<i32> = (1..=5).collect();
+        ll.reverse();
+        assert_eq!(ll, linked_list![5, 4, 3, 2, 1]);
+
+        let mut rest = ll.split_off(2).unwrap();
+        assert_eq!(ll, linked_list![5, 4]);
+        assert_eq!(rest, linked_list![3, 2, 1]);
+        assert_eq!(rest.split_off(3), Ok(linked_list![]));
+        assert_eq!(
+            rest.split_off(4),
+            Err(IndexOutOfBounds { index: 4, len: 3 })
+        );
+
+        ll.append(&mut rest);
+        assert!(rest.is_empty());
+        assert_eq!(ll, linked_list![5, 4, 3, 2, 1]);
+        rest.append(&mut ll);
+        assert_eq!(rest.len(), 5);
+
+        let mut empty: LinkedList<i32> = linked_list![];
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_and_retain() {
+        let mut ll = linked_list![1, 1, 1, 2, 2, 3, 1, 1];
+        ll.dedup();
+        assert_eq!(ll, linked_list![1, 2, 3, 1]);
+
+        // dedup_by compares with the last kept element
+        let mut ll = linked_list![1, 2, 3, 5, 6, 8, 9, 10];
+        ll.dedup_by(|a, b| *a - *b <= 2);
+        assert_eq!(ll, linked_list![1, 5, 8]);
+
+        let mut ll: LinkedList<i32> = (1..=10).collect();
+        ll.retain(|x| x % 3 != 0);
+        assert_eq!(ll, linked_list![1, 2, 4, 5, 7, 8, 10]);
+        ll.retain(|_| false);
+        assert!(ll.is_empty());
+    }
+
+    #[test]
+    fn test_middle_and_nth_from_end() {
+        for n in 0..10 {
+            let ll: LinkedList<usize> = (0..n).collect();
+            assert_eq!(ll.find_middle(), (n > 0).then_some(&(n / 2)));
+            for k in 0..n + 2 {
+                assert_eq!(ll.nth_from_end(k), (k < n).then(|| n - 1 - k).as_ref());
+            }
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        for start in 0..8 {
+            for len in 1..8 {
+                // a rho shaped sequence: `start` nodes, then a cycle of `len` nodes
+                let next = |node: usize| {
+                    Some(if node + 1 == start + len {
+                        start
+                    } else {
+                        node + 1
+                    })
+                };
+                let expected = Some(Cycle {
+                    entry: start,
+                    start,
+                    len,
+                });
+                assert_eq!(find_cycle_floyd(0, next), expected);
+                assert_eq!(find_cycle_brent(0, next), expected);
+
+                let mut arena = NodeArena::new();
+                let head = arena.insert_list(0..start + len).unwrap();
+                assert_eq!(arena.find_cycle(head), None);
+                assert_eq!(find_cycle_floyd(head, |node| arena.next(node)), None);
+                let last = NodeId(arena.len() - 1);
+                arena.link(last, Some(NodeId(start)));
+                let cycle = arena.find_cycle(head).unwrap();
+                assert_eq!(
+                    (arena[cycle.entry], cycle.start, cycle.len),
+                    (start, start, len)
+                );
+            }
+        }
+        // a single node that links to itself
+        let mut arena = NodeArena::new();
+        let node = arena.insert('a');
+        arena[node] = 'b';
+        arena.link(node, Some(node));
+        assert_eq!(
+            find_cycle_floyd(node, |node| arena.next(node)),
+            Some(Cycle {
+                entry: node,
+                start: 0,
+                len: 1
+            })
+        );
+        assert_eq!(NodeArena::<i32>::new().insert_list(None), None);
+    }
 }
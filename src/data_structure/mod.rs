@@ -1,8 +1,20 @@
+pub mod aggregate_stack;
 pub mod binary_tree;
+pub mod blocking_queue;
+pub mod bounded_stack;
+pub mod concurrent_stack;
+pub mod delay_queue;
 pub mod doubly_linked_list;
 pub mod hash_map;
 pub mod heap;
 pub mod linked_list;
+pub mod persistent_stack;
 pub mod queue;
+pub mod raw_doubly_linked_list;
+pub mod ring_queue;
 pub mod skip_list;
+pub mod slab;
+pub mod spsc_queue;
 pub mod stack;
+pub mod timer_wheel;
+pub mod unrolled_list;
@@ -0,0 +1,277 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+/// a persistent (immutable) stack.
+///
+/// `push` does not change the stack, it returns a new stack whose tail is
+/// shared with the old one through `Rc`. so `push`, `pop`, `peek` and `clone`
+/// are all O(1), and every version of the stack stays valid, which makes
+/// snapshots free, e.g. for backtracking search.
+///
+/// it is the same cons-cell design as [`LinkedList`](super::linked_list::LinkedList),
+/// except that the cells are shared instead of owned, so it can also be used as a
+/// persistent list, see [`PList`].
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::persistent_stack::PStack;
+///
+/// let empty = PStack::new();
+/// let a = empty.push(1);
+/// let b = a.push(2);
+/// let c = a.push(3); // shares `1` with `b`
+///
+/// assert_eq!(b.iter().collect::<Vec<_>>(), vec![&2, &1]);
+/// assert_eq!(c.iter().collect::<Vec<_>>(), vec![&3, &1]);
+/// assert_eq!(c.pop(), Some((&3, a.clone())));
+/// assert!(empty.is_empty());
+/// ```
+pub struct PStack<T> {
+    head: Option<Rc<Node<T>>>,
+}
+
+/// a persistent singly linked list, the same structure as [`PStack`]
+pub type PList<T> = PStack<T>;
+
+struct Node<T> {
+    data: T,
+    // the number of elements from this node to the bottom
+    len: usize,
+    next: Option<Rc<Node<T>>>,
+}
+
+impl<T> PStack<T> {
+    #[inline]
+    pub fn new() -> Self {
+        PStack { head: None }
+    }
+
+    /// return a new stack with `data` on the top of this stack
+    #[inline]
+    pub fn push(&self, data: T) -> Self {
+        PStack {
+            head: Some(Rc::new(Node {
+                data,
+                len: self.len() + 1,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    /// the top element and the stack below it, or `None` if the stack is empty
+    #[inline]
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        self.head.as_ref().map(|node| {
+            (
+                &node.data,
+                PStack {
+                    head: node.next.clone(),
+                },
+            )
+        })
+    }
+
+    /// the stack below the top element, or `None` if the stack is empty
+    #[inline]
+    pub fn tail(&self) -> Option<Self> {
+        self.pop().map(|(_, tail)| tail)
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.data)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// the number of elements, in O(1)
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.head.as_ref().map_or(0, |node| node.len)
+    }
+
+    /// whether the two stacks are the same version, i.e. share all their nodes
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// iterate over the elements from the top to the bottom
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T: Clone> PStack<T> {
+    /// a new stack with the elements in reverse order, in O(n)
+    pub fn reverse(&self) -> Self {
+        let mut res = PStack::new();
+        for item in self.iter() {
+            res = res.push(item.clone());
+        }
+        res
+    }
+}
+
+/// iterator over the elements of a [`PStack`], from the top to the bottom
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.next.map_or(0, |node| node.len);
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a PStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// push the items in order, so the last item ends up on the top
+impl<T> FromIterator<T> for PStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = PStack::new();
+        for item in iter {
+            res = res.push(item);
+        }
+        res
+    }
+}
+
+/// cloning only clones the `Rc` of the top node
+impl<T> Clone for PStack<T> {
+    fn clone(&self) -> Self {
+        PStack {
+            head: self.head.clone(),
+        }
+    }
+}
+
+impl<T> Default for PStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// drop the nodes that are not shared with another stack in a loop,
+/// the default recursive drop would overflow the stack on long stacks
+impl<T> Drop for PStack<T> {
+    fn drop(&mut self) {
+        let mut node = self.head.take();
+        while let Some(rc) = node {
+            match Rc::try_unwrap(rc) {
+                Ok(mut n) => node = n.next.take(),
+                // the rest is shared with another stack
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for PStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Debug> Debug for PStack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// display the stack from the top to the bottom, as `PStack[top, ..., bottom]`
+impl<T: Display> Display for PStack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PStack[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persistence() {
+        let a: PStack<i32> = (1..=3).collect();
+        let b = a.push(4);
+        let (top, c) = b.pop().unwrap();
+        assert_eq!(top, &4);
+        // every version is still valid
+        assert_eq!(a.to_string(), "PStack[3, 2, 1]");
+        assert_eq!(b.to_string(), "PStack[4, 3, 2, 1]");
+        assert!(c.ptr_eq(&a));
+        assert_eq!(b.len(), 4);
+        assert_eq!(b.iter().len(), 4);
+        assert_eq!(a.tail().unwrap().peek(), Some(&2));
+    }
+
+    #[test]
+    fn test_empty() {
+        let s: PStack<i32> = PStack::default();
+        assert!(s.is_empty());
+        assert_eq!(s.len(), 0);
+        assert_eq!(s.peek(), None);
+        assert_eq!(s.pop(), None);
+        assert_eq!(s.tail(), None);
+        assert_eq!(s, PStack::new());
+    }
+
+    #[test]
+    fn test_eq_and_reverse() {
+        let a: PList<i32> = vec![1, 2, 3].into_iter().collect();
+        let b: PList<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(a, b);
+        assert!(!a.ptr_eq(&b));
+        assert_ne!(a, a.tail().unwrap());
+        assert_eq!(a.reverse(), vec![3, 2, 1].into_iter().collect());
+        assert_eq!(format!("{:?}", a), "[3, 2, 1]");
+    }
+
+    #[test]
+    fn test_long_stack_drop() {
+        let mut s = PStack::new();
+        for i in 0..1_000_000 {
+            s = s.push(i);
+        }
+        let shared = s.tail().unwrap();
+        drop(s);
+        assert_eq!(shared.len(), 999_999);
+        drop(shared);
+    }
+}
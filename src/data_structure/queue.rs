@@ -1,14 +1,214 @@
-use super::doubly_linked_list::DoublyLinkedList;
+use super::aggregate_stack::{Aggregate, Sum};
+use super::doubly_linked_list::{self, DoublyLinkedList};
+use super::heap::{Max, Min};
+use super::ring_queue::{self, RingQueue};
+use super::stack::Stack;
 use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::Add;
 
-/// `Queue` is a generic queue structure that utilizes a doubly linked list as its underlying data structure.
-/// It provides basic queue operations such as enqueue (to add an element to the end of the queue),
-/// dequeue (to remove and return an element from the beginning of the queue), and checking if the queue is empty.
+/// The storage a [`Queue`] keeps its elements in, a double-ended sequence.
+///
+/// It is implemented by [`DoublyLinkedList`], the default, and by [`RingQueue`],
+/// which keeps the elements in one contiguous buffer instead of allocating a node
+/// for every element, and can be sent to other threads.
+pub trait QueueBackend<T>: IntoIterator<Item = T> {
+    /// An iterator over the elements, from the front to the back.
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+    /// An iterator over mutable references to the elements, from the front to the back.
+    type IterMut<'a>: Iterator<Item = &'a mut T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+    /// Returns `true` if there are no elements.
+    fn is_empty(&self) -> bool;
+    /// Appends an element at the back, the end a queue enqueues at.
+    fn push_back(&mut self, data: T);
+    /// Prepends an element at the front, before every other element.
+    fn push_front(&mut self, data: T);
+    /// Removes and returns the element at the front, the end a queue dequeues from.
+    fn pop_front(&mut self) -> Option<T>;
+    /// Removes and returns the element at the back, the one pushed last by `push_back`.
+    fn pop_back(&mut self) -> Option<T>;
+    /// Returns a reference to the element at the front, the next one `pop_front` returns.
+    fn front(&self) -> Option<&T>;
+    /// Returns a mutable reference to the element at the front.
+    fn front_mut(&mut self) -> Option<&mut T>;
+    /// Returns an iterator over the elements, from the front to the back.
+    fn iter(&self) -> Self::Iter<'_>;
+    /// Returns an iterator over mutable references to the elements, from the front to the back.
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+    /// Keeps only the elements for which `keep` returns `true`, calling it from the
+    /// front to the back and keeping the order of the kept elements.
+    fn retain<F: FnMut(&T) -> bool>(&mut self, keep: F);
+    /// Removes all elements.
+    fn clear(&mut self);
+}
+
+impl<T> QueueBackend<T> for DoublyLinkedList<T> {
+    type Iter<'a>
+        = doubly_linked_list::Iter<'a, T>
+    where
+        T: 'a;
+    type IterMut<'a>
+        = doubly_linked_list::IterMut<'a, T>
+    where
+        T: 'a;
+
+    #[inline]
+    fn len(&self) -> usize {
+        DoublyLinkedList::len(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        DoublyLinkedList::is_empty(self)
+    }
+
+    #[inline]
+    fn push_back(&mut self, data: T) {
+        DoublyLinkedList::push_back(self, data)
+    }
+
+    #[inline]
+    fn push_front(&mut self, data: T) {
+        DoublyLinkedList::push_front(self, data)
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<T> {
+        DoublyLinkedList::pop_front(self)
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<T> {
+        DoublyLinkedList::pop_back(self)
+    }
+
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        DoublyLinkedList::front(self)
+    }
+
+    #[inline]
+    fn front_mut(&mut self) -> Option<&mut T> {
+        DoublyLinkedList::front_mut(self)
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        DoublyLinkedList::iter(self)
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        DoublyLinkedList::iter_mut(self)
+    }
+
+    #[inline]
+    fn retain<F: FnMut(&T) -> bool>(&mut self, keep: F) {
+        DoublyLinkedList::retain(self, keep)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        DoublyLinkedList::clear(self)
+    }
+}
+
+impl<T> QueueBackend<T> for RingQueue<T> {
+    type Iter<'a>
+        = ring_queue::Iter<'a, T>
+    where
+        T: 'a;
+    type IterMut<'a>
+        = ring_queue::IterMut<'a, T>
+    where
+        T: 'a;
+
+    #[inline]
+    fn len(&self) -> usize {
+        RingQueue::len(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        RingQueue::is_empty(self)
+    }
+
+    #[inline]
+    fn push_back(&mut self, data: T) {
+        RingQueue::push_back(self, data)
+    }
+
+    #[inline]
+    fn push_front(&mut self, data: T) {
+        RingQueue::push_front(self, data)
+    }
+
+    #[inline]
+    fn pop_front(&mut self) -> Option<T> {
+        RingQueue::pop_front(self)
+    }
+
+    #[inline]
+    fn pop_back(&mut self) -> Option<T> {
+        RingQueue::pop_back(self)
+    }
+
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        RingQueue::peek_front(self)
+    }
+
+    #[inline]
+    fn front_mut(&mut self) -> Option<&mut T> {
+        RingQueue::peek_front_mut(self)
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        RingQueue::iter(self)
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        RingQueue::iter_mut(self)
+    }
+
+    #[inline]
+    fn retain<F: FnMut(&T) -> bool>(&mut self, keep: F) {
+        RingQueue::retain(self, keep)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        RingQueue::clear(self)
+    }
+}
+
+/// `Queue` is a generic first-in first-out queue. `enqueue` adds an element at the back,
+/// and `dequeue` removes the element at the front.
+///
+/// The elements are kept in a [`QueueBackend`], the second type parameter. It defaults to a
+/// [`DoublyLinkedList`]; `Queue<T, RingQueue<T>>` keeps them in a [`RingQueue`] instead, one
+/// contiguous buffer.
+///
+/// Besides `enqueue` and `dequeue`, it provides `len`, `is_empty`, `peek`, `peek_mut`,
+/// `contains`, `retain` and `clear`, iteration from the front to the back, and deque mode
+/// with `push_front` and `pop_back`.
 ///
 /// # Examples
 ///
 /// ```
 /// use rs_algorithm_practise::data_structure::queue::Queue;
+/// use rs_algorithm_practise::data_structure::ring_queue::RingQueue;
 ///
 /// let mut queue = Queue::new();
 /// assert!(queue.is_empty());
@@ -20,27 +220,71 @@ use std::fmt::{Display, Formatter};
 /// assert_eq!(queue.dequeue(), Some(1));
 /// assert_eq!(queue.dequeue(), Some(2));
 /// assert!(queue.is_empty());
+///
+/// // breadth-first search over the numbers below 10, reached by doubling or adding 3
+/// let mut seen = vec![false; 10];
+/// let mut frontier: Queue<u32> = [1].into_iter().collect();
+/// seen[1] = true;
+/// while let Some(x) = frontier.dequeue() {
+///     for next in [x * 2, x + 3] {
+///         if next < 10 && !seen[next as usize] {
+///             seen[next as usize] = true;
+///             frontier.enqueue(next);
+///         }
+///     }
+///     assert!(frontier.len() <= 10);
+/// }
+/// let reached: Vec<usize> = (0..10).filter(|&i| seen[i]).collect();
+/// assert_eq!(reached, vec![1, 2, 4, 5, 7, 8]);
+///
+/// // the same queue, backed by a ring buffer
+/// let mut queue = Queue::from_backend(RingQueue::with_capacity(16));
+/// queue.enqueue(1);
+/// assert_eq!(queue.dequeue(), Some(1));
 /// ```
-#[derive(Default, Debug, Clone, PartialEq)]
-pub struct Queue<T> {
-    data: DoublyLinkedList<T>,
+#[derive(Debug, Clone, PartialEq)]
+pub struct Queue<T, B = DoublyLinkedList<T>> {
+    data: B,
+    phantom: PhantomData<T>,
 }
 
-impl<T: Display> Display for Queue<T> {
+impl<T, B: Display> Display for Queue<T, B> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.data)
     }
 }
 
+impl<T, B: QueueBackend<T> + Default> Default for Queue<T, B> {
+    fn default() -> Self {
+        Self::from_backend(B::default())
+    }
+}
+
 impl<T> Queue<T> {
-    /// Creates a new, empty `Queue`.
+    /// Creates a new, empty `Queue` backed by a doubly linked list.
     #[inline]
     pub fn new() -> Self {
+        Self::from_backend(DoublyLinkedList::new())
+    }
+}
+
+impl<T, B: QueueBackend<T>> Queue<T, B> {
+    /// Creates a `Queue` that stores its elements in `backend`.
+    /// The elements already in the backend are in the queue, the front first.
+    #[inline]
+    pub fn from_backend(backend: B) -> Self {
         Queue {
-            data: DoublyLinkedList::new(),
+            data: backend,
+            phantom: PhantomData,
         }
     }
 
+    /// Returns the storage of the queue.
+    #[inline]
+    pub fn into_backend(self) -> B {
+        self.data
+    }
+
     /// Checks if the queue is empty.
     ///
     /// # Returns
@@ -71,6 +315,465 @@ impl<T> Queue<T> {
     pub fn dequeue(&mut self) -> Option<T> {
         self.data.pop_front()
     }
+
+    /// Returns the number of elements in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a reference to the front element, the next one to be dequeued,
+    /// or `None` if the queue is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.front()
+    }
+
+    /// Returns a mutable reference to the front element, or `None` if the queue is empty.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.data.front_mut()
+    }
+
+    /// Adds an element to the front of the queue, so it is dequeued next.
+    /// With [`Queue::pop_back`], the queue can be used as a deque.
+    #[inline]
+    pub fn push_front(&mut self, data: T) {
+        self.data.push_front(data)
+    }
+
+    /// Removes and returns the element at the end of the queue,
+    /// the one enqueued last, or `None` if the queue is empty.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.data.pop_back()
+    }
+
+    /// Returns an iterator over the elements, in the order they would be dequeued.
+    #[inline]
+    pub fn iter(&self) -> B::Iter<'_> {
+        self.data.iter()
+    }
+
+    /// Returns an iterator over mutable references to the elements,
+    /// in the order they would be dequeued.
+    #[inline]
+    pub fn iter_mut(&mut self) -> B::IterMut<'_> {
+        self.data.iter_mut()
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`, in order.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, keep: F) {
+        self.data.retain(keep)
+    }
+
+    /// Removes all elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+}
+
+impl<T: PartialEq, B: QueueBackend<T>> Queue<T, B> {
+    /// Returns `true` if the queue contains an element equal to `x`.
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool {
+        self.iter().any(|item| item == x)
+    }
+}
+
+impl<T, B: QueueBackend<T>> IntoIterator for Queue<T, B> {
+    type Item = T;
+    type IntoIter = B::IntoIter;
+
+    /// Returns an iterator over the elements, in the order they would be dequeued.
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T, B: QueueBackend<T>> IntoIterator for &'a Queue<T, B> {
+    type Item = &'a T;
+    type IntoIter = B::Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, B: QueueBackend<T>> IntoIterator for &'a mut Queue<T, B> {
+    type Item = &'a mut T;
+    type IntoIter = B::IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Enqueues the items in order.
+impl<T, B: QueueBackend<T> + Default> FromIterator<T> for Queue<T, B> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::default();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// Enqueues the items in order.
+impl<T, B: QueueBackend<T>> Extend<T> for Queue<T, B> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.enqueue(item);
+        }
+    }
+}
+
+/// The order a [`MonotonicQueue`] keeps the extremum of, implemented by [`Max`] and [`Min`].
+pub trait Extremum<T> {
+    /// Returns `true` if `new` is at least as extreme as `old`, so `old`, which
+    /// leaves the queue first, can never be the extremum again.
+    fn supersedes(new: &T, old: &T) -> bool;
+}
+
+impl<T: Ord> Extremum<T> for Max {
+    #[inline]
+    fn supersedes(new: &T, old: &T) -> bool {
+        new >= old
+    }
+}
+
+impl<T: Ord> Extremum<T> for Min {
+    #[inline]
+    fn supersedes(new: &T, old: &T) -> bool {
+        new <= old
+    }
+}
+
+pub type MaxQueue<T> = MonotonicQueue<T, Max>;
+pub type MinQueue<T> = MonotonicQueue<T, Min>;
+
+/// A FIFO queue that answers the maximum (or minimum) of its elements in O(1),
+/// e.g. the maximum of the last `k` values of a time series.
+///
+/// It only keeps the elements that can still become the extremum: when an element
+/// is pushed, the elements before it that it supersedes are dropped, since they
+/// leave the queue before it. The kept elements are therefore monotonic, and the
+/// extremum is the front one. Every element is pushed and dropped at most once,
+/// so `push` is amortized O(1), and `pop` and `max`/`min` are O(1).
+///
+/// As the dropped elements are gone, `pop` only reports whether an element was
+/// removed and does not return it.
+///
+/// # Examples
+///
+/// ```
+/// use rs_algorithm_practise::data_structure::queue::MaxQueue;
+///
+/// // the maximum of every window of 3 values
+/// let values = [1, 3, -1, -3, 5, 3, 6, 7];
+/// let mut window = MaxQueue::new();
+/// let mut maxima = Vec::new();
+/// for (i, &value) in values.iter().enumerate() {
+///     window.push(value);
+///     if i >= 3 {
+///         window.pop();
+///     }
+///     if i >= 2 {
+///         maxima.push(*window.max().unwrap());
+///     }
+/// }
+/// assert_eq!(maxima, vec![3, 3, 5, 5, 6, 7]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonotonicQueue<T, O> {
+    // the elements that can still become the extremum, with their sequence numbers
+    candidates: RingQueue<(usize, T)>,
+    // the sequence number of the oldest element in the queue
+    front: usize,
+    // the sequence number of the next pushed element
+    back: usize,
+    phantom: PhantomData<O>,
+}
+
+impl<T, O: Extremum<T>> Default for MonotonicQueue<T, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O: Extremum<T>> MonotonicQueue<T, O> {
+    /// Creates a new, empty `MonotonicQueue`.
+    #[inline]
+    pub fn new() -> Self {
+        MonotonicQueue {
+            candidates: RingQueue::new(),
+            front: 0,
+            back: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Adds an element to the back of the queue, in amortized O(1).
+    pub fn push(&mut self, item: T) {
+        while let Some((_, last)) = self.candidates.peek_back() {
+            if !O::supersedes(&item, last) {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((self.back, item));
+        self.back += 1;
+    }
+
+    /// Removes the element at the front of the queue. Returns `false` if the queue was empty.
+    pub fn pop(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        if matches!(self.candidates.peek_front(), Some((seq, _)) if *seq == self.front) {
+            self.candidates.pop_front();
+        }
+        self.front += 1;
+        true
+    }
+
+    /// Returns the extremum of the elements, or `None` if the queue is empty.
+    #[inline]
+    pub fn extremum(&self) -> Option<&T> {
+        self.candidates.peek_front().map(|(_, item)| item)
+    }
+
+    /// Returns the number of elements in the queue, including the dropped ones.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.back - self.front
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.back == self.front
+    }
+
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+        self.front = self.back;
+    }
+}
+
+impl<T: Ord> MaxQueue<T> {
+    /// Returns the largest element, in O(1).
+    #[inline]
+    pub fn max(&self) -> Option<&T> {
+        self.extremum()
+    }
+}
+
+impl<T: Ord> MinQueue<T> {
+    /// Returns the smallest element, in O(1).
+    #[inline]
+    pub fn min(&self) -> Option<&T> {
+        self.extremum()
+    }
+}
+
+pub type SumWindow<T> = SlidingWindow<T, Sum>;
+pub type MinWindow<T> = SlidingWindow<T, Min>;
+pub type MaxWindow<T> = SlidingWindow<T, Max>;
+
+/// The last `size` elements of a stream, and their aggregate: sum, mean, minimum,
+/// maximum, or any associative operation (see [`Aggregate`]).
+///
+/// The window is a queue made of two [`AggregateStack`]-like stacks: elements are
+/// pushed onto the back stack, and popped from the front stack. When the front
+/// stack is empty, the back stack is moved onto it, which reverses the order so
+/// the oldest element is on top. Each stack keeps the running aggregate of its
+/// elements, so the aggregate of the window combines the two, and `push` is
+/// amortized O(1) and `aggregate` is O(1).
+///
+/// The operation does not need an inverse, so minimum and maximum work the same
+/// way as a sum, and a sum of floats does not drift, since the elements that leave
+/// the window are never subtracted. The operation is applied in the order the elements arrived, the
+/// older elements on the left, so it does not need to be commutative either.
+///
+/// [`AggregateStack`]: super::aggregate_stack::AggregateStack
+///
+/// # Examples
+///
+/// ```
+/// use rs_algorithm_practise::data_structure::queue::{MaxWindow, SlidingWindow, SumWindow};
+///
+/// // a moving average of the last 3 samples
+/// let mut average = SumWindow::new(3);
+/// let smoothed: Vec<f64> = [1.0, 2.0, 6.0, 4.0, 8.0]
+///     .into_iter()
+///     .map(|sample| {
+///         average.push(sample);
+///         average.mean().unwrap()
+///     })
+///     .collect();
+/// assert_eq!(smoothed, vec![1.0, 1.5, 3.0, 4.0, 6.0]);
+///
+/// let mut peak = MaxWindow::new(2);
+/// peak.push(5);
+/// peak.push(1);
+/// assert_eq!(peak.push(3), Some(5));
+/// assert_eq!(peak.max(), Some(3));
+///
+/// // any associative operation, here the concatenation of the last 3 words
+/// let mut words = SlidingWindow::with_aggregate(3, |a: &String, b: &String| a.clone() + b);
+/// for word in ["a", "b", "c", "d"] {
+///     words.push(word.to_string());
+/// }
+/// assert_eq!(words.aggregate().as_deref(), Some("bcd"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlidingWindow<T, A> {
+    // the older elements, the oldest on top. each element is stored with the
+    // aggregate of itself and the newer elements below it
+    front: Stack<(T, T)>,
+    // the newer elements, the newest on top. each element is stored with the
+    // aggregate of the older elements below it and itself
+    back: Stack<(T, T)>,
+    size: usize,
+    op: A,
+}
+
+impl<T: Clone, A: Aggregate<T>> SlidingWindow<T, A> {
+    /// Creates an empty window of the last `size` elements, aggregated with `op`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn with_aggregate(size: usize, op: A) -> Self {
+        assert!(size > 0, "the size of a sliding window must be positive");
+        SlidingWindow {
+            front: Stack::new(),
+            back: Stack::new(),
+            size,
+            op,
+        }
+    }
+
+    /// Adds an element to the window. Returns the oldest element if it left the window.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let evicted = if self.is_full() { self.pop() } else { None };
+        let aggregate = match self.back.peek() {
+            Some((_, older)) => self.op.combine(older, &item),
+            None => item.clone(),
+        };
+        self.back.push((item, aggregate));
+        evicted
+    }
+
+    /// Removes the oldest element from the window and returns it, or `None` if the window is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some((item, _)) = self.back.pop() {
+                let aggregate = match self.front.peek() {
+                    Some((_, newer)) => self.op.combine(&item, newer),
+                    None => item.clone(),
+                };
+                self.front.push((item, aggregate));
+            }
+        }
+        self.front.pop().map(|(item, _)| item)
+    }
+
+    /// Returns the aggregate of the elements in the window, or `None` if it is empty.
+    pub fn aggregate(&self) -> Option<T> {
+        match (self.front.peek(), self.back.peek()) {
+            (Some((_, older)), Some((_, newer))) => Some(self.op.combine(older, newer)),
+            (Some((_, aggregate)), None) | (None, Some((_, aggregate))) => Some(aggregate.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the number of elements in the window, at most its size.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.size() + self.back.size()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Returns `true` if the window holds `size` elements, so the next push evicts one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.size
+    }
+
+    /// Returns the maximum number of elements in the window.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    /// Returns an iterator over the elements, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.front
+            .iter()
+            .chain(self.back.iter().rev())
+            .map(|(item, _)| item)
+    }
+}
+
+impl<T: Add<Output = T> + Clone> SumWindow<T> {
+    /// Creates an empty window of the last `size` elements that adds them up.
+    #[inline]
+    pub fn new(size: usize) -> Self {
+        Self::with_aggregate(size, Sum)
+    }
+
+    /// Returns the sum of the elements, or `None` if the window is empty.
+    #[inline]
+    pub fn sum(&self) -> Option<T> {
+        self.aggregate()
+    }
+
+    /// Returns the mean of the elements, or `None` if the window is empty.
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Into<f64>,
+    {
+        self.sum().map(|sum| sum.into() / self.len() as f64)
+    }
+}
+
+impl<T: Ord + Clone> MinWindow<T> {
+    /// Creates an empty window of the last `size` elements that tracks their minimum.
+    #[inline]
+    pub fn new(size: usize) -> Self {
+        Self::with_aggregate(size, Min)
+    }
+
+    /// Returns the smallest element, or `None` if the window is empty.
+    #[inline]
+    pub fn min(&self) -> Option<T> {
+        self.aggregate()
+    }
+}
+
+impl<T: Ord + Clone> MaxWindow<T> {
+    /// Creates an empty window of the last `size` elements that tracks their maximum.
+    #[inline]
+    pub fn new(size: usize) -> Self {
+        Self::with_aggregate(size, Max)
+    }
+
+    /// Returns the largest element, or `None` if the window is empty.
+    #[inline]
+    pub fn max(&self) -> Option<T> {
+        self.aggregate()
+    }
 }
 
 #[cfg(test)]
@@ -88,4 +791,143 @@ mod tests {
         assert_eq!(q.dequeue(), None);
         assert!(q.is_empty());
     }
+
+    #[test]
+    fn test_ring_queue_backend() {
+        let mut q: Queue<i32, RingQueue<i32>> = Queue::default();
+        for i in 0..100 {
+            q.enqueue(i);
+        }
+        for i in 0..50 {
+            assert_eq!(q.dequeue(), Some(i));
+        }
+        assert_eq!(
+            q.to_string(),
+            format!("{}", (50..100).collect::<RingQueue<_>>())
+        );
+
+        // a ring-backed queue can be moved to another thread
+        let q = std::thread::spawn(move || {
+            q.enqueue(100);
+            q
+        })
+        .join()
+        .unwrap();
+        let rest: Vec<i32> = q.into_backend().into_iter().collect();
+        assert_eq!(rest, (50..=100).collect::<Vec<_>>());
+    }
+
+    fn check_deque_api<B: QueueBackend<i32> + Default + std::fmt::Debug>() {
+        let mut q: Queue<i32, B> = (1..=5).collect();
+        assert_eq!(q.len(), 5);
+        assert_eq!(q.peek(), Some(&1));
+        if let Some(front) = q.peek_mut() {
+            *front = 0;
+        }
+        q.push_front(-1);
+        assert_eq!(q.pop_back(), Some(5));
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 2, 3, 4]);
+
+        for x in &mut q {
+            *x *= 2;
+        }
+        q.retain(|x| *x != 4);
+        q.extend([10, 12]);
+        assert!(q.contains(&12));
+        assert!(!q.contains(&4));
+        assert_eq!((&q).into_iter().count(), q.len());
+        assert_eq!(q.dequeue(), Some(-2));
+        assert_eq!(q.into_iter().collect::<Vec<_>>(), vec![0, 6, 8, 10, 12]);
+
+        let mut q: Queue<i32, B> = Queue::default();
+        q.extend([1, 2]);
+        q.clear();
+        assert!(q.is_empty());
+        assert_eq!(q.len(), 0);
+        assert_eq!(q.peek(), None);
+        assert_eq!(q.pop_back(), None);
+    }
+
+    #[test]
+    fn test_deque_api() {
+        check_deque_api::<DoublyLinkedList<i32>>();
+        check_deque_api::<RingQueue<i32>>();
+    }
+
+    #[test]
+    fn test_monotonic_queue() {
+        let mut queue = MinQueue::new();
+        assert_eq!(queue.min(), None);
+        assert!(!queue.pop());
+        for x in [5, 3, 4, 3, 6] {
+            queue.push(x);
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.min(), Some(&3));
+        // 5, then the first 3, then 4
+        assert!(queue.pop());
+        assert!(queue.pop());
+        assert_eq!(queue.min(), Some(&3));
+        assert!(queue.pop());
+        assert!(queue.pop());
+        assert_eq!(queue.min(), Some(&6));
+        queue.clear();
+        assert!(queue.is_empty());
+        queue.push(1);
+        assert_eq!(queue.min(), Some(&1));
+
+        // compare with a brute force sliding maximum
+        let values: Vec<i32> = (0..200).map(|i| (i * 37 + 11) % 23 - (i % 7) * 3).collect();
+        for k in 1..8 {
+            let mut window = MaxQueue::new();
+            for (i, &value) in values.iter().enumerate() {
+                window.push(value);
+                if i >= k {
+                    assert!(window.pop());
+                }
+                let start = (i + 1).saturating_sub(k);
+                assert_eq!(window.max(), values[start..=i].iter().max());
+                assert_eq!(window.len(), i + 1 - start);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let mut window = SumWindow::new(3);
+        assert_eq!(window.sum(), None);
+        assert_eq!(window.mean(), None);
+        for x in 1..=3 {
+            assert_eq!(window.push(x), None);
+        }
+        assert!(window.is_full());
+        assert_eq!(window.sum(), Some(6));
+        assert_eq!(window.push(4), Some(1));
+        assert_eq!(window.push(5), Some(2));
+        assert_eq!(window.iter().collect::<Vec<_>>(), vec![&3, &4, &5]);
+        assert_eq!(window.mean(), Some(4.0));
+        assert_eq!(window.pop(), Some(3));
+        assert_eq!(window.len(), 2);
+        assert_eq!(window.sum(), Some(9));
+        window.clear();
+        assert!(window.is_empty());
+
+        // compare with brute force, with a non-commutative operation
+        let concat = |a: &String, b: &String| format!("{}{}", a, b);
+        let letters: Vec<String> = ('a'..='z').map(String::from).collect();
+        let mut min = MinWindow::new(4);
+        let mut max = MaxWindow::new(4);
+        let mut words = SlidingWindow::with_aggregate(4, concat);
+        let values: Vec<i32> = (0..100).map(|i| (i * 53 + 7) % 31).collect();
+        for (i, &value) in values.iter().enumerate() {
+            min.push(value);
+            max.push(value);
+            words.push(letters[i % 26].clone());
+            let start = (i + 1).saturating_sub(4);
+            assert_eq!(min.min(), values[start..=i].iter().min().copied());
+            assert_eq!(max.max(), values[start..=i].iter().max().copied());
+            let expected: String = (start..=i).map(|j| letters[j % 26].as_str()).collect();
+            assert_eq!(words.aggregate(), Some(expected));
+        }
+    }
 }
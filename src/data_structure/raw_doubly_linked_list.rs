@@ -0,0 +1,1253 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// A doubly linked list whose nodes link to each other with raw pointers.
+///
+/// It has the same API as [`DoublyLinkedList`], but every node is a plain
+/// `Box` that the list owns, linked to its neighbours by `NonNull` pointers.
+/// So no operation updates a reference count or checks a `RefCell` borrow
+/// flag, and like a `Vec`, the list is `Send` and `Sync` when `T` is.
+///
+/// [`DoublyLinkedList`]: super::doubly_linked_list::DoublyLinkedList
+///
+/// # Examples
+///
+/// ```
+/// use rs_algorithm_practise::data_structure::raw_doubly_linked_list::RawDoublyLinkedList;
+/// use rs_algorithm_practise::raw_doubly_linked_list;
+/// use std::thread;
+///
+/// let mut list = raw_doubly_linked_list![3, 1, 2];
+/// list.push_front(4);
+/// list.sort();
+/// // the list can be moved to another thread
+/// let handle = thread::spawn(move || list.into_iter().rev().collect::<Vec<_>>());
+/// assert_eq!(handle.join().unwrap(), vec![4, 3, 2, 1]);
+/// ```
+pub struct RawDoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // the list owns the boxed nodes, for the drop check
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    data: T,
+    next: Link<T>,
+    prev: Link<T>,
+}
+
+type NodePtr<T> = NonNull<Node<T>>;
+type Link<T> = Option<NodePtr<T>>;
+
+// SAFETY: the list owns its nodes like a `Box<T>` owns its value, and the
+// nodes are only reached through the list, so it can be sent or shared
+// whenever `T` can.
+unsafe impl<T: Send> Send for RawDoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for RawDoublyLinkedList<T> {}
+
+// allocate a node, that is not linked to any other node yet
+fn new_node<T>(data: T) -> NodePtr<T> {
+    NonNull::from(Box::leak(Box::new(Node {
+        data,
+        next: None,
+        prev: None,
+    })))
+}
+
+impl<T> RawDoublyLinkedList<T> {
+    /// Creates a new empty list.
+    #[inline]
+    pub const fn new() -> Self {
+        RawDoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Checks whether the list is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the list, in `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a reference to the first element, or `None` if the list is empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: the nodes are owned by the list, and borrowed along with it
+        self.head.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns a mutable reference to the first element, or `None` if the list is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `front`
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns a reference to the last element, or `None` if the list is empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: as in `front`
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns a mutable reference to the last element, or `None` if the list is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `front`
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns a cursor at the first element, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the first element that can edit the list,
+    /// see [`CursorMut`].
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element that can edit the list,
+    /// see [`CursorMut`].
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns an iterator over the elements, from the front to the back.
+    /// It can also be walked from the back with `rev` or `next_back`.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements, from the front to the back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Removes all elements, in `O(n)`.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`, in order.
+    /// The nodes of the removed elements are unlinked, the others are not moved.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut node = self.head;
+        while let Some(current) = node {
+            // SAFETY: `current` is a node of the list, and the reference passed
+            // to `keep` ends before the node is unlinked
+            unsafe {
+                node = (*current.as_ptr()).next;
+                if !keep(&(*current.as_ptr()).data) {
+                    drop(self.unlink(current));
+                }
+            }
+        }
+    }
+
+    /// Adds a new element at the front of the list.
+    pub fn push_front(&mut self, data: T) {
+        let node = new_node(data);
+        // SAFETY: the node is new, and `head` has no previous node
+        unsafe { self.link_chain(node, node, 1, None, self.head) }
+    }
+
+    /// Removes the first element from the list and returns it,
+    /// or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: `head` is a node of the list
+        self.head.map(|node| unsafe { self.unlink(node).data })
+    }
+
+    /// Adds a new element at the end of the list.
+    pub fn push_back(&mut self, data: T) {
+        let node = new_node(data);
+        // SAFETY: the node is new, and `tail` has no next node
+        unsafe { self.link_chain(node, node, 1, self.tail, None) }
+    }
+
+    /// Removes the last element from the list and returns it,
+    /// or `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: `tail` is a node of the list
+        self.tail.map(|node| unsafe { self.unlink(node).data })
+    }
+
+    /// Sorts the list in place with a bottom-up merge sort.
+    ///
+    /// The sort is stable and takes `O(n log n)` time. It relinks the existing
+    /// nodes, so no element is moved or reallocated. If `compare` panics, the
+    /// elements are leaked instead of dropped.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // runs[i] is either empty or a sorted run of 2^i nodes,
+        // and runs in higher slots hold earlier nodes
+        let mut runs: [Link<T>; usize::BITS as usize] = [None; usize::BITS as usize];
+
+        let mut rest = self.take_chain();
+        // SAFETY: the nodes of the chain were taken out of the list, and
+        // each of them is in exactly one of `rest`, `runs` and `carry`
+        unsafe {
+            while let Some(node) = rest {
+                rest = (*node.as_ptr()).next.take();
+                let mut carry = Some(node);
+                let mut i = 0;
+                while let Some(run) = runs[i].take() {
+                    carry = merge_chains(Some(run), carry, &mut compare);
+                    i += 1;
+                }
+                runs[i] = carry;
+            }
+
+            let mut res = None;
+            for run in runs {
+                res = merge_chains(run, res, &mut compare);
+            }
+            self.set_chain(res);
+        }
+    }
+
+    /// Sorts the list in place with insertion sort.
+    ///
+    /// The sort is stable and takes `O(n^2)` time, which is fine for short lists.
+    /// Like [`RawDoublyLinkedList::sort_by`], it only relinks the nodes.
+    pub fn insertion_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut sorted: Link<T> = None;
+        let mut rest = self.take_chain();
+        // SAFETY: the nodes of the chain were taken out of the list, and
+        // each of them is in exactly one of `rest` and `sorted`
+        unsafe {
+            while let Some(node) = rest {
+                rest = (*node.as_ptr()).next;
+                // find the last node that is not greater than the new one
+                let mut prev: Link<T> = None;
+                let mut cursor = sorted;
+                while let Some(current) = cursor {
+                    if compare(&(*current.as_ptr()).data, &(*node.as_ptr()).data)
+                        == Ordering::Greater
+                    {
+                        break;
+                    }
+                    cursor = (*current.as_ptr()).next;
+                    prev = Some(current);
+                }
+                match prev {
+                    Some(prev) => {
+                        (*node.as_ptr()).next = (*prev.as_ptr()).next;
+                        (*prev.as_ptr()).next = Some(node);
+                    }
+                    None => {
+                        (*node.as_ptr()).next = sorted;
+                        sorted = Some(node);
+                    }
+                }
+            }
+            self.set_chain(sorted);
+        }
+    }
+
+    /// Merges another sorted list into this sorted list, keeping it sorted.
+    ///
+    /// The nodes of `other` are relinked into this list. On equal elements,
+    /// the elements of this list come first.
+    pub fn merge_by<F>(&mut self, mut other: RawDoublyLinkedList<T>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // SAFETY: both chains were taken out of their lists
+        unsafe {
+            let res = merge_chains(self.take_chain(), other.take_chain(), &mut compare);
+            self.set_chain(res);
+        }
+    }
+
+    // take the nodes out of the list as a chain linked by `next`, the caller
+    // must link them into a list again or they are leaked
+    fn take_chain(&mut self) -> Link<T> {
+        self.tail = None;
+        self.len = 0;
+        self.head.take()
+    }
+
+    // make a chain linked by `next` the content of the empty list,
+    // restoring the `prev` pointers, the last node and the length.
+    // the nodes of the chain must not belong to any list
+    unsafe fn set_chain(&mut self, chain: Link<T>) {
+        let mut prev = None;
+        let mut len = 0;
+        let mut node = chain;
+        while let Some(current) = node {
+            (*current.as_ptr()).prev = prev;
+            prev = Some(current);
+            node = (*current.as_ptr()).next;
+            len += 1;
+        }
+        self.head = chain;
+        self.tail = prev;
+        self.len = len;
+    }
+
+    // take all the nodes out of the list, as its first and last node and its length
+    fn take_ends(&mut self) -> Option<(NodePtr<T>, NodePtr<T>, usize)> {
+        let len = self.len;
+        let tail = self.tail;
+        let head = self.take_chain();
+        head.zip(tail).map(|(head, tail)| (head, tail, len))
+    }
+
+    // link the chain of `len` nodes from `first` to `last` between `prev` and
+    // `next`, which must be adjacent nodes of the list, or `None` at the ends.
+    // the nodes of the chain must not belong to any list
+    unsafe fn link_chain(
+        &mut self,
+        first: NodePtr<T>,
+        last: NodePtr<T>,
+        len: usize,
+        prev: Link<T>,
+        next: Link<T>,
+    ) {
+        (*first.as_ptr()).prev = prev;
+        (*last.as_ptr()).next = next;
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(first),
+            None => self.head = Some(first),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(last),
+            None => self.tail = Some(last),
+        }
+        self.len += len;
+    }
+
+    // unlink a node of this list, linking its neighbours to each other,
+    // and give back the ownership of the node
+    unsafe fn unlink(&mut self, node: NodePtr<T>) -> Box<Node<T>> {
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+        node
+    }
+}
+
+impl<T: PartialEq> RawDoublyLinkedList<T> {
+    /// Returns `true` if the list contains an element equal to `x`, in `O(n)`.
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool {
+        self.iter().any(|item| item == x)
+    }
+}
+
+impl<T: Ord> RawDoublyLinkedList<T> {
+    /// Sorts the list in ascending order, see [`RawDoublyLinkedList::sort_by`].
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp)
+    }
+
+    /// Sorts the list in ascending order, see [`RawDoublyLinkedList::insertion_sort_by`].
+    #[inline]
+    pub fn insertion_sort(&mut self) {
+        self.insertion_sort_by(T::cmp)
+    }
+
+    /// Merges another list sorted in ascending order into this one,
+    /// see [`RawDoublyLinkedList::merge_by`].
+    #[inline]
+    pub fn merge(&mut self, other: RawDoublyLinkedList<T>) {
+        self.merge_by(other, T::cmp)
+    }
+}
+
+/// Merges two sorted chains linked by `next` only, relinking their nodes.
+/// On equal elements, the nodes of `a` come first.
+///
+/// The nodes of the chains must not belong to any list.
+unsafe fn merge_chains<T, F>(mut a: Link<T>, mut b: Link<T>, compare: &mut F) -> Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head: Link<T> = None;
+    let mut tail: Link<T> = None;
+    loop {
+        let node = match (a, b) {
+            (Some(x), Some(y)) => {
+                if compare(&(*y.as_ptr()).data, &(*x.as_ptr()).data) == Ordering::Less {
+                    b = (*y.as_ptr()).next;
+                    y
+                } else {
+                    a = (*x.as_ptr()).next;
+                    x
+                }
+            }
+            // one of the chains is exhausted, append the other one
+            (rest, None) | (None, rest) => {
+                match tail {
+                    Some(tail) => (*tail.as_ptr()).next = rest,
+                    None => head = rest,
+                }
+                return head;
+            }
+        };
+        match tail {
+            Some(tail) => (*tail.as_ptr()).next = Some(node),
+            None => head = Some(node),
+        }
+        tail = Some(node);
+    }
+}
+
+impl<T> Drop for RawDoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for RawDoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over the elements of a [`RawDoublyLinkedList`], from the front to the back.
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    // the number of elements left between `head` and `tail`, both included
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+// SAFETY: the iterator only hands out shared references, like `&'a T`
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| {
+            // SAFETY: the list is borrowed for `'a`, so the node lives that long
+            unsafe {
+                self.head = (*node.as_ptr()).next;
+                self.len -= 1;
+                &(*node.as_ptr()).data
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| {
+            // SAFETY: as in `next`
+            unsafe {
+                self.tail = (*node.as_ptr()).prev;
+                self.len -= 1;
+                &(*node.as_ptr()).data
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// An iterator over mutable references to the elements of a [`RawDoublyLinkedList`],
+/// from the front to the back.
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    // the number of elements left between `head` and `tail`, both included
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: the iterator hands out unique references, like `&'a mut T`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| {
+            // SAFETY: the list is borrowed mutably for `'a`, and every node is
+            // visited once, as `len` stops `head` and `tail` where they meet.
+            // only `data` is borrowed, so the links can still be read
+            unsafe {
+                self.head = (*node.as_ptr()).next;
+                self.len -= 1;
+                &mut (*node.as_ptr()).data
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| {
+            // SAFETY: as in `next`
+            unsafe {
+                self.tail = (*node.as_ptr()).prev;
+                self.len -= 1;
+                &mut (*node.as_ptr()).data
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a [`RawDoublyLinkedList`], from the front to the back.
+pub struct IntoIter<T> {
+    list: RawDoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for RawDoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RawDoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RawDoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Pushes the items to the back in order.
+impl<T> FromIterator<T> for RawDoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = RawDoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Pushes the items to the back in order.
+impl<T> Extend<T> for RawDoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+/// A cursor over a [`RawDoublyLinkedList`], that can move forward and backward.
+///
+/// It works like the [`Cursor`] of [`DoublyLinkedList`]: it points at an
+/// element, or at the "ghost" position between the back and the front.
+///
+/// [`Cursor`]: super::doubly_linked_list::Cursor
+/// [`DoublyLinkedList`]: super::doubly_linked_list::DoublyLinkedList
+pub struct Cursor<'a, T> {
+    current: Link<T>,
+    // the index of the current element, or the length of the list at the ghost position
+    index: usize,
+    list: &'a RawDoublyLinkedList<T>,
+}
+
+// SAFETY: the cursor only reads the list, like a `&'a RawDoublyLinkedList<T>`
+unsafe impl<T: Sync> Send for Cursor<'_, T> {}
+unsafe impl<T: Sync> Sync for Cursor<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the index of the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves to the next element, from the back to the ghost position,
+    /// and from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                // SAFETY: `node` is a node of the list
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves to the previous element, from the front to the ghost position,
+    /// and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => {
+                // SAFETY: `node` is a node of the list
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = match self.current {
+                    Some(_) => self.index - 1,
+                    None => self.list.len,
+                };
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for `'a`
+        self.current.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    /// Returns the next element, the front at the ghost position.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for `'a`
+        unsafe {
+            let next = match self.current {
+                Some(node) => (*node.as_ptr()).next,
+                None => self.list.head,
+            };
+            next.map(|node| &(*node.as_ptr()).data)
+        }
+    }
+
+    /// Returns the previous element, the back at the ghost position.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for `'a`
+        unsafe {
+            let prev = match self.current {
+                Some(node) => (*node.as_ptr()).prev,
+                None => self.list.tail,
+            };
+            prev.map(|node| &(*node.as_ptr()).data)
+        }
+    }
+}
+
+/// A cursor over a [`RawDoublyLinkedList`] that can also edit the list around it.
+///
+/// Besides moving like a [`Cursor`], it inserts and removes elements at its
+/// position, and splits or splices whole lists there, each in `O(1)`.
+///
+/// # Examples
+///
+/// ```
+/// use rs_algorithm_practise::data_structure::raw_doubly_linked_list::RawDoublyLinkedList;
+/// use rs_algorithm_practise::raw_doubly_linked_list;
+///
+/// let mut list: RawDoublyLinkedList<i32> = (1..=5).collect();
+/// let mut cursor = list.cursor_front_mut();
+/// cursor.move_next();
+/// cursor.move_next();
+/// // rotate the list left by two
+/// let front = cursor.split_before();
+/// cursor.move_prev();
+/// cursor.splice_before(front);
+/// assert_eq!(list, raw_doubly_linked_list![3, 4, 5, 1, 2]);
+/// ```
+pub struct CursorMut<'a, T> {
+    current: Link<T>,
+    // the index of the current element, or the length of the list at the ghost position
+    index: usize,
+    list: &'a mut RawDoublyLinkedList<T>,
+}
+
+// SAFETY: the cursor edits the list, like a `&'a mut RawDoublyLinkedList<T>`
+unsafe impl<T: Send> Send for CursorMut<'_, T> {}
+unsafe impl<T: Sync> Sync for CursorMut<'_, T> {}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the index of the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Returns a read-only cursor at the same position.
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.current,
+            index: self.index,
+            list: self.list,
+        }
+    }
+
+    /// Moves to the next element, see [`Cursor::move_next`].
+    pub fn move_next(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_next();
+        (self.current, self.index) = (cursor.current, cursor.index);
+    }
+
+    /// Moves to the previous element, see [`Cursor::move_prev`].
+    pub fn move_prev(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_prev();
+        (self.current, self.index) = (cursor.current, cursor.index);
+    }
+
+    /// Returns the current element, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the list and the cursor are borrowed mutably
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Returns the next element, the front at the ghost position.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `current`
+        unsafe {
+            let next = match self.current {
+                Some(node) => (*node.as_ptr()).next,
+                None => self.list.head,
+            };
+            next.map(|node| &mut (*node.as_ptr()).data)
+        }
+    }
+
+    /// Returns the previous element, the back at the ghost position.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `current`
+        unsafe {
+            let prev = match self.current {
+                Some(node) => (*node.as_ptr()).prev,
+                None => self.list.tail,
+            };
+            prev.map(|node| &mut (*node.as_ptr()).data)
+        }
+    }
+
+    /// Inserts an element after the current one, or at the front at the ghost position.
+    pub fn insert_after(&mut self, data: T) {
+        let node = new_node(data);
+        // SAFETY: the node is new, and it is linked between adjacent nodes
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let next = (*current.as_ptr()).next;
+                    self.list.link_chain(node, node, 1, Some(current), next);
+                }
+                None => {
+                    self.list.link_chain(node, node, 1, None, self.list.head);
+                    self.index += 1;
+                }
+            }
+        }
+    }
+
+    /// Inserts an element before the current one, or at the back at the ghost position.
+    pub fn insert_before(&mut self, data: T) {
+        let node = new_node(data);
+        // SAFETY: the node is new, and it is linked between adjacent nodes
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let prev = (*current.as_ptr()).prev;
+                    self.list.link_chain(node, node, 1, prev, Some(current));
+                }
+                None => self.list.link_chain(node, node, 1, self.list.tail, None),
+            }
+        }
+        self.index += 1;
+    }
+
+    /// Removes the current element and returns it, moving to the next one.
+    /// Returns `None` and does nothing at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        // SAFETY: `current` is a node of the list
+        unsafe {
+            self.current = (*current.as_ptr()).next;
+            Some(self.list.unlink(current).data)
+        }
+    }
+
+    /// Splits the list after the current element, and returns the elements after it.
+    /// At the ghost position, returns the whole list.
+    pub fn split_after(&mut self) -> RawDoublyLinkedList<T> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return std::mem::take(self.list);
+        };
+        // SAFETY: `current` and `next` are adjacent nodes of the list, and the
+        // nodes from `next` to the tail are moved to the new list
+        unsafe {
+            let Some(next) = (*current.as_ptr()).next.take() else {
+                return RawDoublyLinkedList::new();
+            };
+            (*next.as_ptr()).prev = None;
+            let split = RawDoublyLinkedList {
+                head: Some(next),
+                tail: self.list.tail.replace(current),
+                len: self.list.len - self.index - 1,
+                marker: PhantomData,
+            };
+            self.list.len = self.index + 1;
+            split
+        }
+    }
+
+    /// Splits the list before the current element, and returns the elements before it.
+    /// At the ghost position, returns the whole list.
+    pub fn split_before(&mut self) -> RawDoublyLinkedList<T> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return std::mem::take(self.list);
+        };
+        // SAFETY: `prev` and `current` are adjacent nodes of the list, and the
+        // nodes from the head to `prev` are moved to the new list
+        unsafe {
+            let Some(prev) = (*current.as_ptr()).prev.take() else {
+                return RawDoublyLinkedList::new();
+            };
+            (*prev.as_ptr()).next = None;
+            let split = RawDoublyLinkedList {
+                head: self.list.head.replace(current),
+                tail: Some(prev),
+                len: self.index,
+                marker: PhantomData,
+            };
+            self.list.len -= self.index;
+            self.index = 0;
+            split
+        }
+    }
+
+    /// Moves the elements of `other` after the current element,
+    /// or to the front at the ghost position.
+    pub fn splice_after(&mut self, mut other: RawDoublyLinkedList<T>) {
+        let Some((first, last, len)) = other.take_ends() else {
+            return;
+        };
+        // SAFETY: the chain was taken out of `other`, and it is linked between
+        // adjacent nodes
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let next = (*current.as_ptr()).next;
+                    self.list.link_chain(first, last, len, Some(current), next);
+                }
+                None => {
+                    self.list.link_chain(first, last, len, None, self.list.head);
+                    self.index += len;
+                }
+            }
+        }
+    }
+
+    /// Moves the elements of `other` before the current element,
+    /// or to the back at the ghost position.
+    pub fn splice_before(&mut self, mut other: RawDoublyLinkedList<T>) {
+        let Some((first, last, len)) = other.take_ends() else {
+            return;
+        };
+        // SAFETY: as in `splice_after`
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let prev = (*current.as_ptr()).prev;
+                    self.list.link_chain(first, last, len, prev, Some(current));
+                }
+                None => self.list.link_chain(first, last, len, self.list.tail, None),
+            }
+        }
+        self.index += len;
+    }
+}
+
+impl<T: Clone> Clone for RawDoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for RawDoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for RawDoublyLinkedList<T> {}
+
+impl<T: Debug> Debug for RawDoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+/// Display the linked list
+impl<T: Display> Display for RawDoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RawDoublyLinkedList(None ⇌")?;
+        for data in self {
+            write!(f, " {} ⇌", data)?;
+        }
+        write!(f, " None)")
+    }
+}
+
+/// Initializes a [`RawDoublyLinkedList`] with the elements in order, from the
+/// front to the back.
+///
+/// # Examples
+///
+/// ```rust
+/// use rs_algorithm_practise::raw_doubly_linked_list;
+///
+/// let list = raw_doubly_linked_list![1, 2, 3];
+/// assert_eq!(list.to_string(), "RawDoublyLinkedList(None ⇌ 1 ⇌ 2 ⇌ 3 ⇌ None)");
+/// ```
+#[macro_export]
+macro_rules! raw_doubly_linked_list {
+    ($($elem:expr),* $(,)?) => {
+        <$crate::data_structure::raw_doubly_linked_list::RawDoublyLinkedList<_>
+            as ::core::iter::FromIterator<_>>::from_iter([$($elem),*])
+    };
+}
+
+// the tests use small inputs, so that they also run under Miri:
+// `cargo +nightly miri test raw_doubly_linked_list`
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::thread;
+
+    // check the links in both directions and the length
+    fn assert_links<T: PartialEq + Debug>(list: &RawDoublyLinkedList<T>, expected: &[T]) {
+        assert_eq!(list.len(), expected.len());
+        assert!(list.iter().eq(expected));
+        assert!(list.iter().rev().eq(expected.iter().rev()));
+        assert_eq!(list.front(), expected.first());
+        assert_eq!(list.back(), expected.last());
+    }
+
+    #[test]
+    fn test_push_and_pop() {
+        let mut list = RawDoublyLinkedList::new();
+        assert_eq!((list.pop_front(), list.pop_back()), (None, None));
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_links(&list, &[1, 2, 3]);
+        *list.front_mut().unwrap() += 10;
+        *list.back_mut().unwrap() += 30;
+        assert_eq!(list.pop_back(), Some(33));
+        assert_eq!(list.pop_front(), Some(11));
+        assert_eq!(list.pop_front(), Some(2));
+        assert!(list.is_empty());
+        assert_links(&list, &[]);
+        list.push_front(4);
+        assert_links(&list, &[4]);
+        assert_eq!(list.to_string(), "RawDoublyLinkedList(None ⇌ 4 ⇌ None)");
+        assert_eq!(format!("{:?}", list), "[4]");
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut list: RawDoublyLinkedList<i32> = (1..=5).collect();
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&1), Some(&5)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!((iter.next_back(), iter.next()), (Some(&4), Some(&2)));
+        assert_eq!((iter.next(), iter.next_back()), (Some(&3), None));
+
+        let mut iter = list.iter_mut();
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            std::mem::swap(front, back);
+        }
+        for x in &mut list {
+            *x *= 10;
+        }
+        assert_links(&list, &[50, 40, 30, 20, 10]);
+        assert!(list.contains(&30));
+        assert!(!list.contains(&3));
+
+        list.extend([0]);
+        let mut iter = list.clone().into_iter();
+        assert_eq!((iter.next_back(), iter.next()), (Some(0), Some(50)));
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+        assert_eq!(list, raw_doubly_linked_list![50, 40, 30, 20, 10, 0]);
+        assert_ne!(list, raw_doubly_linked_list![50, 40, 30, 20, 10]);
+    }
+
+    #[test]
+    fn test_sort_and_merge() {
+        let mut list = raw_doubly_linked_list![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        let mut copy = list.clone();
+        let sorted = [(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')];
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_links(&list, &sorted);
+        copy.insertion_sort_by(|a, b| a.0.cmp(&b.0));
+        assert_links(&copy, &sorted);
+
+        let mut list: RawDoublyLinkedList<i32> = (0..50).map(|i| (i * 37) % 50).collect();
+        list.sort();
+        assert_links(&list, &(0..50).collect::<Vec<_>>());
+
+        let mut list = raw_doubly_linked_list![1, 3, 5];
+        list.merge(raw_doubly_linked_list![2, 3, 4, 6, 7]);
+        assert_links(&list, &[1, 2, 3, 3, 4, 5, 6, 7]);
+        let mut empty = RawDoublyLinkedList::new();
+        empty.insertion_sort();
+        empty.merge(list);
+        assert_links(&empty, &[1, 2, 3, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_retain_and_clear() {
+        let mut list: RawDoublyLinkedList<i32> = (1..=6).collect();
+        list.retain(|x| x % 3 != 0);
+        assert_links(&list, &[1, 2, 4, 5]);
+        list.retain(|x| x % 2 == 0);
+        assert_links(&list, &[2, 4]);
+        list.clear();
+        assert_links(&list, &[]);
+        list.push_back(7);
+        assert_links(&list, &[7]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut list = raw_doubly_linked_list![2, 4];
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(1);
+        cursor.insert_after(3);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        cursor.move_prev();
+        cursor.move_prev();
+        // at the ghost position, insert at the ends
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        cursor.insert_after(0);
+        cursor.insert_before(5);
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        assert_links(&list, &[0, 1, 2, 3, 4, 5]);
+
+        let mut cursor = list.cursor_back();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&0)));
+        assert_eq!((cursor.peek_prev(), cursor.peek_next()), (None, Some(&1)));
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.as_cursor().current(), Some(&2));
+        assert_links(&list, &[0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_cursor_split_and_splice() {
+        let mut list: RawDoublyLinkedList<i32> = (0..6).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_links(&before, &[0, 1]);
+        assert_links(&after, &[3, 4, 5]);
+        assert_links(&list, &[2]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(after);
+        cursor.splice_before(before);
+        cursor.splice_before(RawDoublyLinkedList::new());
+        assert_eq!(cursor.index(), Some(2));
+        assert_links(&list, &[0, 1, 2, 3, 4, 5]);
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        cursor.splice_after(raw_doubly_linked_list![-1]);
+        cursor.splice_before(raw_doubly_linked_list![6, 7]);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(8));
+        assert!(cursor.split_after().is_empty());
+        cursor.move_next();
+        let all = cursor.split_before();
+        assert!(list.is_empty());
+        assert_links(&all, &[-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let token = Rc::new(());
+        {
+            let mut list: RawDoublyLinkedList<Rc<()>> = (0..10).map(|_| token.clone()).collect();
+            list.retain(|_| false);
+            list.extend((0..10).map(|_| token.clone()));
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            drop(cursor.remove_current());
+            let tail = cursor.split_after();
+            // an iterator dropped halfway drops the rest
+            let mut iter = tail.into_iter();
+            iter.next();
+            iter.next_back();
+            assert_eq!(Rc::strong_count(&token), 1 + 2 + 5);
+        }
+        assert_eq!(Rc::strong_count(&token), 1);
+
+        // a unit element type works with sort and pop
+        let mut units: RawDoublyLinkedList<()> = (0..3).map(|_| ()).collect();
+        units.sort();
+        assert_eq!(units.pop_back(), Some(()));
+        assert_eq!(units.len(), 2);
+    }
+
+    #[test]
+    fn test_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RawDoublyLinkedList<i32>>();
+        assert_send_sync::<Iter<'_, i32>>();
+        assert_send_sync::<IterMut<'_, i32>>();
+        assert_send_sync::<IntoIter<i32>>();
+        assert_send_sync::<Cursor<'_, i32>>();
+        assert_send_sync::<CursorMut<'_, i32>>();
+
+        let mut list: RawDoublyLinkedList<i32> = (0..10).collect();
+        thread::scope(|s| {
+            let (left, right) = (&list, &list);
+            let forward = s.spawn(move || left.iter().sum::<i32>());
+            let backward = s.spawn(move || right.iter().rev().sum::<i32>());
+            assert_eq!(forward.join().unwrap(), backward.join().unwrap());
+        });
+        thread::scope(|s| {
+            s.spawn(|| list.iter_mut().for_each(|x| *x *= 2));
+        });
+        let list = thread::spawn(move || list.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(
+            list.join().unwrap(),
+            (0..10).rev().map(|x| x * 2).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Chain;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+/// the capacity of the first allocation
+const MIN_CAPACITY: usize = 4;
+
+/// a double-ended queue stored in a growable ring buffer.
+///
+/// the elements live in one contiguous buffer: the front is at `head`, and the
+/// queue wraps around to the start of the buffer when it reaches the end. when
+/// the buffer is full, it is reallocated with twice the capacity, so pushing is
+/// amortized O(1), and popping and indexing are O(1) without any allocation.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::ring_queue::RingQueue;
+///
+/// let mut queue = RingQueue::with_capacity(2);
+/// queue.push_back(1);
+/// queue.push_back(2);
+/// assert_eq!(queue.pop_front(), Some(1));
+/// queue.push_back(3); // wraps around, no reallocation
+/// assert_eq!(queue.capacity(), 2);
+/// queue.push_front(0); // full, grows
+///
+/// assert_eq!(queue.len(), 3);
+/// assert_eq!(queue.get(1), Some(&2));
+/// assert_eq!(queue.peek_back(), Some(&3));
+/// assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&0, &2, &3]);
+/// ```
+#[derive(Clone)]
+pub struct RingQueue<T> {
+    buffer: Box<[Option<T>]>,
+    // the index of the front element in the buffer
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingQueue<T> {
+    /// create an empty queue, without allocating
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// create an empty queue that can hold `capacity` elements without reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        RingQueue {
+            buffer: empty_buffer(capacity),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the number of elements the queue can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// add an element to the back of the queue, in amortized O(1)
+    pub fn push_back(&mut self, data: T) {
+        self.grow_if_full();
+        let index = self.physical(self.len);
+        self.buffer[index] = Some(data);
+        self.len += 1;
+    }
+
+    /// add an element to the front of the queue, in amortized O(1)
+    pub fn push_front(&mut self, data: T) {
+        self.grow_if_full();
+        self.head = self.physical(self.capacity() - 1);
+        self.buffer[self.head] = Some(data);
+        self.len += 1;
+    }
+
+    /// remove the front element and return it, or `None` if the queue is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let data = self.buffer[self.head].take();
+        self.head = self.physical(1);
+        self.len -= 1;
+        data
+    }
+
+    /// remove the back element and return it, or `None` if the queue is empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let index = self.physical(self.len);
+        self.buffer[index].take()
+    }
+
+    #[inline]
+    pub fn peek_front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    #[inline]
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    #[inline]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    #[inline]
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.len.checked_sub(1).and_then(|i| self.get_mut(i))
+    }
+
+    /// the `i`-th element from the front, in O(1)
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i < self.len {
+            self.buffer[self.physical(i)].as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// a mutable reference to the `i`-th element from the front, in O(1)
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i < self.len {
+            let index = self.physical(i);
+            self.buffer[index].as_mut()
+        } else {
+            None
+        }
+    }
+
+    /// keep only the elements for which `keep` returns `true`, in order
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // rotate every element through the queue once, the elements that are
+        // pushed back always fit, since they were popped first
+        for _ in 0..self.len {
+            if let Some(item) = self.pop_front() {
+                if keep(&item) {
+                    self.push_back(item);
+                }
+            }
+        }
+    }
+
+    /// remove all elements, keeping the capacity
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.head = 0;
+    }
+
+    /// iterate over the elements from the front to the back
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.split_at_head();
+        Iter {
+            inner: front.iter().chain(back.iter()),
+        }
+    }
+
+    /// iterate mutably over the elements from the front to the back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, len) = (self.head, self.len);
+        let (before, after) = self.buffer.split_at_mut(head);
+        let front_len = len.min(after.len());
+        IterMut {
+            inner: after[..front_len]
+                .iter_mut()
+                .chain(before[..len - front_len].iter_mut()),
+        }
+    }
+
+    // the buffer index of the `i`-th element from the front, `i` is at most the capacity
+    #[inline]
+    fn physical(&self, i: usize) -> usize {
+        let index = self.head + i;
+        if index >= self.capacity() {
+            index - self.capacity()
+        } else {
+            index
+        }
+    }
+
+    // the elements, split into the part from `head` to the end of the buffer,
+    // and the part that wrapped around to the start
+    fn split_at_head(&self) -> (&[Option<T>], &[Option<T>]) {
+        let front_len = self.len.min(self.capacity() - self.head);
+        (
+            &self.buffer[self.head..self.head + front_len],
+            &self.buffer[..self.len - front_len],
+        )
+    }
+
+    // double the capacity if the buffer is full, and move the elements
+    // to the start of the new buffer
+    fn grow_if_full(&mut self) {
+        if self.len < self.capacity() {
+            return;
+        }
+        let capacity = (self.capacity() * 2).max(MIN_CAPACITY);
+        let mut buffer = Vec::with_capacity(capacity);
+        for i in 0..self.len {
+            let index = self.physical(i);
+            buffer.push(self.buffer[index].take());
+        }
+        buffer.resize_with(capacity, || None);
+        self.buffer = buffer.into_boxed_slice();
+        self.head = 0;
+    }
+}
+
+fn empty_buffer<T>(capacity: usize) -> Box<[Option<T>]> {
+    (0..capacity).map(|_| None).collect()
+}
+
+impl<T> Default for RingQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Panics
+/// if the index is out of bounds
+impl<T> Index<usize> for RingQueue<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        self.get(i).expect("index out of bounds")
+    }
+}
+
+/// # Panics
+/// if the index is out of bounds
+impl<T> IndexMut<usize> for RingQueue<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        self.get_mut(i).expect("index out of bounds")
+    }
+}
+
+/// two queues are equal if they have the same elements in the same order,
+/// wherever the elements are in the buffers
+impl<T: PartialEq> PartialEq for RingQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RingQueue<T> {}
+
+impl<T: Debug> Debug for RingQueue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// display the queue from the front to the back, as `RingQueue[front, ..., back]`
+impl<T: Display> Display for RingQueue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RingQueue[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// iterator over the elements of a [`RingQueue`], from the front to the back
+pub struct Iter<'a, T> {
+    inner: Chain<slice::Iter<'a, Option<T>>, slice::Iter<'a, Option<T>>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    // the slots between the front and the back are never `None`
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().and_then(Option::as_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().and_then(Option::as_ref)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// mutable iterator over the elements of a [`RingQueue`], from the front to the back
+pub struct IterMut<'a, T> {
+    inner: Chain<slice::IterMut<'a, Option<T>>, slice::IterMut<'a, Option<T>>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().and_then(Option::as_mut)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().and_then(Option::as_mut)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// owning iterator over the elements of a [`RingQueue`], from the front to the back
+pub struct IntoIter<T> {
+    queue: RingQueue<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.queue.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for RingQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// push the items to the back in order
+impl<T> FromIterator<T> for RingQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = RingQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// push the items to the back in order
+impl<T> Extend<T> for RingQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue() {
+        let mut queue = RingQueue::new();
+        assert_eq!(queue.capacity(), 0);
+        assert_eq!(queue.pop_front(), None);
+        for i in 0..10 {
+            queue.push_back(i);
+        }
+        assert_eq!(queue.len(), 10);
+        assert!(queue.capacity() >= 10);
+        for i in 0..10 {
+            assert_eq!(queue.pop_front(), Some(i));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut queue = RingQueue::with_capacity(4);
+        // move the head around the buffer a few times without growing
+        for i in 0..20 {
+            queue.push_back(i);
+            if queue.len() == 3 {
+                assert_eq!(queue.pop_front(), Some(i - 2));
+            }
+        }
+        assert_eq!(queue.capacity(), 4);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![18, 19]);
+    }
+
+    #[test]
+    fn test_grow_while_wrapped() {
+        let mut queue: RingQueue<i32> = RingQueue::with_capacity(4);
+        queue.extend([1, 2, 3]);
+        queue.pop_front();
+        queue.pop_front();
+        // the elements are at the end and at the start of the buffer
+        queue.extend([4, 5, 6]);
+        assert_eq!(queue.capacity(), 4);
+        queue.push_back(7);
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue, (3..=7).collect());
+        assert_eq!(queue[0], 3);
+        assert_eq!(queue.get(4), Some(&7));
+        assert_eq!(queue.get(5), None);
+    }
+
+    #[test]
+    fn test_double_ended() {
+        let mut queue = RingQueue::new();
+        queue.push_front(2);
+        queue.push_front(1);
+        queue.push_back(3);
+        assert_eq!(queue.to_string(), "RingQueue[1, 2, 3]");
+        assert_eq!(queue.peek_back(), Some(&3));
+        assert_eq!(queue.pop_back(), Some(3));
+        assert_eq!(queue.pop_back(), Some(2));
+        assert_eq!(queue.pop_front(), Some(1));
+        assert_eq!(queue.pop_back(), None);
+        assert_eq!(queue.peek_back(), None);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut queue: RingQueue<i32> = RingQueue::with_capacity(3);
+        queue.extend([0, 1, 2]);
+        queue.pop_front();
+        queue.push_back(3);
+
+        for item in &mut queue {
+            *item *= 10;
+        }
+        if let Some(back) = queue.peek_back_mut() {
+            *back += 1;
+        }
+        queue[0] += 2;
+        assert_eq!(queue.iter().len(), 3);
+        assert_eq!(queue.iter().rev().collect::<Vec<_>>(), vec![&31, &20, &12]);
+        assert_eq!(format!("{:?}", queue), "[12, 20, 31]");
+        assert_eq!(
+            queue.clone().into_iter().collect::<Vec<_>>(),
+            vec![12, 20, 31]
+        );
+        assert_eq!(
+            queue.into_iter().rev().collect::<Vec<_>>(),
+            vec![31, 20, 12]
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut queue: RingQueue<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let capacity = queue.capacity();
+        queue.retain(|s| s != "b");
+        assert_eq!(queue.to_string(), "RingQueue[a, c]");
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), capacity);
+        queue.push_back("d".to_string());
+        assert_eq!(queue.peek_front().map(String::as_str), Some("d"));
+    }
+}
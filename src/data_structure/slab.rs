@@ -0,0 +1,197 @@
+use std::fmt::{Debug, Formatter};
+
+/// a handle to an element of a [`Slab`].
+///
+/// a key stays valid until its element is removed. the slot may then be reused
+/// for another element, but with a new generation, so the old key does not
+/// find the new element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key {
+    index: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u64,
+    value: Option<T>,
+}
+
+/// a collection that hands out a [`Key`] for every inserted element, and finds
+/// or removes the element by its key in O(1).
+///
+/// the elements are stored in a `Vec`, and the slots of removed elements are
+/// kept in a free list and reused by later inserts.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::slab::Slab;
+///
+/// let mut slab = Slab::new();
+/// let a = slab.insert("a");
+/// let b = slab.insert("b");
+/// assert_eq!(slab.remove(a), Some("a"));
+/// // the slot of `a` is reused, but `a` does not find the new element
+/// let c = slab.insert("c");
+/// assert_eq!(slab.get(a), None);
+/// assert_eq!(slab.get(c), Some(&"c"));
+/// assert_eq!(slab.len(), 2);
+/// assert_eq!(slab.remove(b), Some("b"));
+/// ```
+#[derive(Clone)]
+pub struct Slab<T> {
+    slots: Vec<Slot<T>>,
+    // the indices of the empty slots
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T> Slab<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Slab {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// insert an element and return its key
+    pub fn insert(&mut self, value: T) -> Key {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.value = Some(value);
+                Key {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                Key {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// remove the element of `key` and return it, or `None` if it was already removed
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let slot = self.slots.get_mut(key.index)?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation += 1;
+        self.free.push(key.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    pub fn get(&self, key: Key) -> Option<&T> {
+        self.slots
+            .get(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        self.slots
+            .get_mut(key.index)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+
+    #[inline]
+    pub fn contains(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// remove every element, the keys handed out so far become invalid
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation += 1;
+                self.free.push(index);
+            }
+        }
+        self.len = 0;
+    }
+
+    /// iterate over the keys and elements, in the order of their slots
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let key = Key {
+                index,
+                generation: slot.generation,
+            };
+            slot.value.as_ref().map(|value| (key, value))
+        })
+    }
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for Slab<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut slab = Slab::new();
+        let keys: Vec<Key> = (0..10).map(|i| slab.insert(i)).collect();
+        assert_eq!(slab.len(), 10);
+        for key in keys.iter().step_by(2) {
+            assert!(slab.remove(*key).is_some());
+            assert_eq!(slab.remove(*key), None);
+        }
+        assert_eq!(slab.len(), 5);
+        *slab.get_mut(keys[1]).unwrap() += 100;
+        assert_eq!(
+            slab.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![101, 3, 5, 7, 9]
+        );
+
+        // reused slots get new keys
+        let new_keys: Vec<Key> = (0..5).map(|i| slab.insert(i * 10)).collect();
+        assert_eq!(slab.slots.len(), 10);
+        for (old, new) in keys.iter().step_by(2).zip(&new_keys) {
+            assert!(!slab.contains(*old));
+            assert!(slab.contains(*new));
+        }
+
+        slab.clear();
+        assert!(slab.is_empty());
+        assert!(!slab.contains(keys[1]));
+        let key = slab.insert(-1);
+        assert_eq!(slab.get(key), Some(&-1));
+    }
+}
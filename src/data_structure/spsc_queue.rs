@@ -0,0 +1,471 @@
+use std::cell::UnsafeCell;
+use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// aligns a value to its own cache line, so that the producer writing one
+/// index does not invalidate the cache line of the consumer writing the other
+/// (false sharing)
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+struct Shared<T> {
+    // the position of the next element to pop, only written by the consumer
+    head: CachePadded<AtomicUsize>,
+    // the position of the next element to push, only written by the producer
+    tail: CachePadded<AtomicUsize>,
+    // the slots from `head` to `tail` are initialized. its length is a power of
+    // two, so positions can wrap around `usize` and still map to the right slot
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    capacity: usize,
+}
+
+// SAFETY: a slot is only accessed by one half at a time: the producer writes the
+// slots outside of `head..tail`, the consumer reads and takes the slots inside
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    #[inline]
+    fn slot(&self, position: usize) -> *mut MaybeUninit<T> {
+        self.buffer[position & (self.buffer.len() - 1)].get()
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        let mut position = head;
+        while position != tail {
+            // SAFETY: the slots from `head` to `tail` are initialized, and both
+            // halves are gone, so nothing else can access them
+            unsafe { (*self.slot(position)).assume_init_drop() };
+            position = position.wrapping_add(1);
+        }
+    }
+}
+
+/// create a bounded single-producer single-consumer queue that holds at most
+/// `capacity` elements, and split it into its two halves.
+///
+/// the halves can be sent to two threads. both `push` and `pop` are wait-free:
+/// they never lock and never retry, they read the index of the other half and
+/// publish their own index with one atomic store. each half also remembers the
+/// last index it read from the other half, and only reads it again when that
+/// cached index says the queue is full (or empty), so most operations do not
+/// touch the other half's cache line at all.
+///
+/// `push_slice` and `pop_slice` move many elements with a single pair of atomic
+/// operations, for handing off batches between pipeline stages.
+///
+/// # Panics
+/// if `capacity` is 0
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::spsc_queue;
+/// use std::thread;
+///
+/// let (mut producer, mut consumer) = spsc_queue::channel(64);
+/// let handle = thread::spawn(move || {
+///     for i in 0..1000 {
+///         // spin until there is room
+///         while producer.push(i).is_err() {
+///             std::hint::spin_loop();
+///         }
+///     }
+/// });
+///
+/// let mut sum = 0;
+/// let mut received = 0;
+/// while received < 1000 {
+///     if let Some(x) = consumer.pop() {
+///         sum += x;
+///         received += 1;
+///     }
+/// }
+/// handle.join().unwrap();
+/// assert_eq!(sum, 999 * 1000 / 2);
+/// ```
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(
+        capacity > 0,
+        "the capacity of a spsc queue must be positive"
+    );
+    let buffer = (0..capacity.next_power_of_two())
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+    let shared = Arc::new(Shared {
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+        buffer,
+        capacity,
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+            tail: 0,
+            cached_head: 0,
+        },
+        Consumer {
+            shared,
+            head: 0,
+            cached_tail: 0,
+        },
+    )
+}
+
+/// the pushing half of a queue created by [`channel`]
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    // the producer's own copy of `shared.tail`
+    tail: usize,
+    // the last `shared.head` seen, the consumer may have moved past it
+    cached_head: usize,
+}
+
+impl<T> Producer<T> {
+    /// push an element, or give it back if the queue is full
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.free_slots(1) == 0 {
+            return Err(item);
+        }
+        // SAFETY: the slot at `tail` is free, and only the producer writes free slots
+        unsafe { (*self.shared.slot(self.tail)).write(item) };
+        self.tail = self.tail.wrapping_add(1);
+        self.shared.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+
+    /// push clones of as many elements from the front of `items` as fit,
+    /// and return how many were pushed
+    pub fn push_slice(&mut self, items: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let count = self.free_slots(items.len());
+        for (i, item) in items[..count].iter().enumerate() {
+            let position = self.tail.wrapping_add(i);
+            // SAFETY: the `count` slots from `tail` are free
+            unsafe { (*self.shared.slot(position)).write(item.clone()) };
+        }
+        self.tail = self.tail.wrapping_add(count);
+        self.shared.tail.store(self.tail, Ordering::Release);
+        count
+    }
+
+    /// the number of elements that can be pushed right now
+    #[inline]
+    pub fn slots(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Acquire);
+        self.shared.capacity - self.tail.wrapping_sub(head)
+    }
+
+    /// whether the queue was full at the time of the call
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.slots() == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// whether the consumer was dropped, so nothing pushed will be popped
+    #[inline]
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+
+    // the number of free slots, at most `wanted`. the consumer's index is only
+    // loaded again if the cached one does not leave enough room
+    #[inline]
+    fn free_slots(&mut self, wanted: usize) -> usize {
+        let free = self.shared.capacity - self.tail.wrapping_sub(self.cached_head);
+        if free >= wanted {
+            return wanted;
+        }
+        self.cached_head = self.shared.head.load(Ordering::Acquire);
+        let free = self.shared.capacity - self.tail.wrapping_sub(self.cached_head);
+        free.min(wanted)
+    }
+}
+
+/// the popping half of a queue created by [`channel`]
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    // the consumer's own copy of `shared.head`
+    head: usize,
+    // the last `shared.tail` seen, the producer may have moved past it
+    cached_tail: usize,
+}
+
+impl<T> Consumer<T> {
+    /// pop the front element, or `None` if the queue is empty
+    pub fn pop(&mut self) -> Option<T> {
+        if self.available(1) == 0 {
+            return None;
+        }
+        // SAFETY: the slot at `head` was initialized by the producer, and the
+        // `Acquire` load of its tail made the write visible
+        let item = unsafe { (*self.shared.slot(self.head)).assume_init_read() };
+        self.head = self.head.wrapping_add(1);
+        self.shared.head.store(self.head, Ordering::Release);
+        Some(item)
+    }
+
+    /// pop as many elements as are available into the front of `out`, overwriting
+    /// the elements there, and return how many were popped
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize {
+        let count = self.available(out.len());
+        let shared = &*self.shared;
+        // the head moves past every element as soon as it is moved out, and is
+        // published once at the end, or while unwinding if dropping an old
+        // element of `out` panics, so no element is dropped twice
+        let head = PublishHead {
+            head: &mut self.head,
+            shared: &shared.head,
+        };
+        for slot in &mut out[..count] {
+            // SAFETY: the `count` slots from `head` are initialized
+            let item = unsafe { (*shared.slot(*head.head)).assume_init_read() };
+            *head.head = head.head.wrapping_add(1);
+            *slot = item;
+        }
+        count
+    }
+
+    /// a reference to the front element, or `None` if the queue is empty
+    pub fn peek(&mut self) -> Option<&T> {
+        if self.available(1) == 0 {
+            return None;
+        }
+        // SAFETY: the slot is initialized, and the producer does not write it
+        // until the consumer moves past it
+        Some(unsafe { (*self.shared.slot(self.head)).assume_init_ref() })
+    }
+
+    /// the number of elements at the time of the call
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared
+            .tail
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.head)
+    }
+
+    /// whether the queue was empty at the time of the call
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// whether the producer was dropped, so nothing more will be pushed
+    #[inline]
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+
+    // the number of available elements, at most `wanted`. the producer's index
+    // is only loaded again if the cached one does not show enough elements
+    #[inline]
+    fn available(&mut self, wanted: usize) -> usize {
+        let available = self.cached_tail.wrapping_sub(self.head);
+        if available >= wanted {
+            return wanted;
+        }
+        self.cached_tail = self.shared.tail.load(Ordering::Acquire);
+        self.cached_tail.wrapping_sub(self.head).min(wanted)
+    }
+}
+
+// stores the consumer's own head into the shared one when dropped
+struct PublishHead<'a> {
+    head: &'a mut usize,
+    shared: &'a AtomicUsize,
+}
+
+impl Drop for PublishHead<'_> {
+    fn drop(&mut self) {
+        self.shared.store(*self.head, Ordering::Release);
+    }
+}
+
+impl<T> Debug for Producer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Producer")
+            .field("slots", &self.slots())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> Debug for Consumer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::thread;
+
+    #[test]
+    fn test_push_and_pop() {
+        let (mut producer, mut consumer) = channel(3);
+        assert_eq!(consumer.pop(), None);
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert_eq!(producer.push(3), Ok(()));
+        // the buffer has 4 slots, but the capacity is 3
+        assert!(producer.is_full());
+        assert_eq!(producer.push(4), Err(4));
+        assert_eq!(consumer.len(), 3);
+        assert_eq!(consumer.peek(), Some(&1));
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.slots(), 1);
+
+        // wrap around the buffer many times
+        for i in 4..100 {
+            assert_eq!(producer.push(i), Ok(()));
+            assert_eq!(consumer.pop(), Some(i - 2));
+        }
+        assert_eq!(consumer.pop(), Some(98));
+        assert_eq!(consumer.pop(), Some(99));
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_slices() {
+        let (mut producer, mut consumer) = channel(5);
+        assert_eq!(producer.push_slice(&[1, 2, 3]), 3);
+        assert_eq!(producer.push_slice(&[4, 5, 6, 7]), 2);
+        assert_eq!(producer.push_slice(&[8]), 0);
+
+        let mut out = [0; 4];
+        assert_eq!(consumer.pop_slice(&mut out), 4);
+        assert_eq!(out, [1, 2, 3, 4]);
+        assert_eq!(producer.push_slice(&[6, 7, 8]), 3);
+        assert_eq!(consumer.pop_slice(&mut out), 4);
+        assert_eq!(out, [5, 6, 7, 8]);
+        assert_eq!(consumer.pop_slice(&mut out), 0);
+    }
+
+    #[test]
+    fn test_across_threads() {
+        const N: u64 = 200_000;
+        let (mut producer, mut consumer) = channel(100);
+        let handle = thread::spawn(move || {
+            let batch: Vec<u64> = (0..10).collect();
+            let mut i = 0;
+            while i < N {
+                // alternate single and batch pushes
+                if i % 20 == 0 {
+                    let chunk: Vec<u64> = batch.iter().map(|x| i + x).collect();
+                    let pushed = producer.push_slice(&chunk);
+                    i += pushed as u64;
+                } else if producer.push(i).is_ok() {
+                    i += 1;
+                } else {
+                    // let the consumer run when there is only one core
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        let mut out = vec![0; 7];
+        while expected < N {
+            let popped = consumer.pop_slice(&mut out);
+            for &x in &out[..popped] {
+                assert_eq!(x, expected);
+                expected += 1;
+            }
+            if let Some(x) = consumer.pop() {
+                assert_eq!(x, expected);
+                expected += 1;
+            } else if popped == 0 {
+                thread::yield_now();
+            }
+        }
+        handle.join().unwrap();
+        assert!(consumer.is_abandoned());
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        let (mut producer, mut consumer) = channel(4);
+        let item = Arc::new(());
+        for _ in 0..4 {
+            producer.push(Arc::clone(&item)).unwrap();
+        }
+        consumer.pop();
+        assert_eq!(Arc::strong_count(&item), 4);
+        drop(producer);
+        assert!(consumer.is_abandoned());
+        drop(consumer);
+        assert_eq!(Arc::strong_count(&item), 1);
+    }
+
+    // counts its drops, and panics on drop if asked to
+    struct Tracked<'a> {
+        id: usize,
+        drops: &'a [Cell<usize>],
+        panic: bool,
+    }
+
+    impl Drop for Tracked<'_> {
+        fn drop(&mut self) {
+            self.drops[self.id].set(self.drops[self.id].get() + 1);
+            if self.panic {
+                panic!("dropping element {} panicked", self.id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pop_slice_panicking_drop() {
+        let drops: Vec<Cell<usize>> = (0..4).map(|_| Cell::new(0)).collect();
+        let tracked = |id, panic| Tracked {
+            id,
+            drops: &drops,
+            panic,
+        };
+        let (mut producer, mut consumer) = channel(4);
+        assert!(producer.push(tracked(0, false)).is_ok());
+        assert!(producer.push(tracked(1, false)).is_ok());
+        let mut out = [tracked(2, false), tracked(3, true)];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| consumer.pop_slice(&mut out)));
+        assert!(result.is_err());
+        // both elements were moved out, so they are no longer in the queue
+        assert!(consumer.pop().is_none());
+        drop(out);
+        drop(producer);
+        drop(consumer);
+        let drops: Vec<usize> = drops.iter().map(Cell::get).collect();
+        assert_eq!(drops, vec![1, 1, 1, 1]);
+    }
+}
@@ -1,7 +1,34 @@
+use std::fmt::{Display, Formatter};
+use std::iter::Rev;
+
+/// a stack backed by a `Vec`, the top of the stack is the end of the `Vec`.
+///
+/// iterating over a stack visits the elements from the top to the bottom,
+/// i.e. in the order they would be popped.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::stack::Stack;
+///
+/// let mut stack: Stack<i32> = (1..=3).collect();
+/// assert_eq!(stack.peek(), Some(&3));
+/// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+/// assert_eq!(stack.to_string(), "Stack[3, 2, 1]");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct Stack<T> {
     elements: Vec<T>,
 }
 
+/// iterator over the elements of a stack, from the top to the bottom
+pub type Iter<'a, T> = Rev<std::slice::Iter<'a, T>>;
+/// mutable iterator over the elements of a stack, from the top to the bottom
+pub type IterMut<'a, T> = Rev<std::slice::IterMut<'a, T>>;
+/// owning iterator over the elements of a stack, from the top to the bottom
+pub type IntoIter<T> = Rev<std::vec::IntoIter<T>>;
+/// draining iterator over the elements of a stack, from the top to the bottom
+pub type Drain<'a, T> = Rev<std::vec::Drain<'a, T>>;
+
 impl<T> Stack<T> {
     #[inline]
     pub fn new() -> Self {
@@ -9,6 +36,13 @@ impl<T> Stack<T> {
             elements: Vec::new(),
         }
     }
+    /// create an empty stack that can hold `capacity` elements without reallocating
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Stack {
+            elements: Vec::with_capacity(capacity),
+        }
+    }
     #[inline]
     pub fn push(&mut self, item: T) {
         self.elements.push(item);
@@ -21,6 +55,11 @@ impl<T> Stack<T> {
     pub fn peek(&self) -> Option<&T> {
         self.elements.last()
     }
+    /// a mutable reference to the top element, if any
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.elements.last_mut()
+    }
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
@@ -29,6 +68,31 @@ impl<T> Stack<T> {
     pub fn size(&self) -> usize {
         self.elements.len()
     }
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+    /// remove all elements
+    #[inline]
+    pub fn clear(&mut self) {
+        self.elements.clear()
+    }
+    /// iterate over the elements from the top to the bottom
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.elements.iter().rev()
+    }
+    /// iterate mutably over the elements from the top to the bottom
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.elements.iter_mut().rev()
+    }
+    /// remove all elements and iterate over them from the top to the bottom.
+    /// the stack is empty afterwards, even if the iterator is not fully consumed.
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.elements.drain(..).rev()
+    }
 }
 
 impl<T> Default for Stack<T> {
@@ -37,6 +101,63 @@ impl<T> Default for Stack<T> {
     }
 }
 
+/// display the stack from the top to the bottom, as `Stack[top, ..., bottom]`
+impl<T: Display> Display for Stack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Stack[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// push the items in order, so the last item ends up on the top
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack {
+            elements: Vec::from_iter(iter),
+        }
+    }
+}
+
+/// push the items in order, so the last item ends up on the top
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.elements.extend(iter)
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Stack;
@@ -58,4 +179,56 @@ mod tests {
         assert_eq!(stack.pop(), Some(1));
         assert!(stack.is_empty());
     }
+
+    #[test]
+    fn test_iter() {
+        let mut stack: Stack<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        for item in &mut stack {
+            *item *= 10;
+        }
+        let mut top_down = Vec::new();
+        for item in &stack {
+            top_down.push(*item);
+        }
+        assert_eq!(top_down, vec![30, 20, 10]);
+
+        // the owning iterator yields the elements in the order of `pop`
+        let cloned = stack.clone();
+        let mut popped = Vec::new();
+        while let Some(item) = stack.pop() {
+            popped.push(item);
+        }
+        assert_eq!(cloned.into_iter().collect::<Vec<_>>(), popped);
+    }
+
+    #[test]
+    fn test_extend_and_drain() {
+        let mut stack = Stack::with_capacity(4);
+        assert!(stack.capacity() >= 4);
+        stack.push('a');
+        stack.extend(['b', 'c']);
+        assert_eq!(stack.peek(), Some(&'c'));
+        assert_eq!(stack.to_string(), "Stack[c, b, a]");
+
+        assert_eq!(stack.drain().collect::<String>(), "cba");
+        assert!(stack.is_empty());
+        assert_eq!(stack.to_string(), "Stack[]");
+    }
+
+    #[test]
+    fn test_peek_mut_and_clear() {
+        let mut stack: Stack<i32> = (1..=3).collect();
+        if let Some(top) = stack.peek_mut() {
+            *top = 42;
+        }
+        assert_eq!(stack.pop(), Some(42));
+        assert_eq!(stack, (1..=2).collect());
+
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.peek_mut(), None);
+        assert_eq!(stack, Stack::default());
+    }
 }
@@ -0,0 +1,409 @@
+use super::ring_queue::RingQueue;
+use super::slab::{Key, Slab};
+use crate::supplementary::clock::{Clock, SystemClock};
+use std::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
+
+// every level has 2^SLOT_BITS slots, and a slot of a level spans as many ticks
+// as the whole level below it
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 6;
+
+struct Entry<T> {
+    item: T,
+    // the tick the item expires at
+    when: u64,
+}
+
+struct Level {
+    slots: Vec<Vec<Key>>,
+    // bit `i` is set if slot `i` is not empty
+    occupied: u64,
+}
+
+/// a hierarchical hashed timer wheel, for many timers at a coarse resolution,
+/// e.g. the timeouts of thousands of requests.
+///
+/// the time is divided into ticks. the wheel has 6 levels of 64 slots: a slot of
+/// level 0 holds the timers of one tick, and a slot of level `n` spans 64^n
+/// ticks. a timer is put on the lowest level whose slots tell it apart from the
+/// current tick, and when the time reaches its slot it is moved down to a lower
+/// level (cascading), until it expires on level 0. timers more than 64^6 ticks
+/// ahead wait in an overflow list. inserting and cancelling a timer is O(1), and
+/// every timer is moved at most 6 times. advancing the time jumps to the next
+/// occupied slot with the occupancy bitmap of each level, so idle time is free.
+///
+/// timers never expire early: a deadline is rounded up to the next tick.
+/// like [`DelayQueue`], every insert returns a [`Key`] to cancel the timer, the
+/// time is read from a [`Clock`], and nothing runs in the background.
+///
+/// [`DelayQueue`]: super::delay_queue::DelayQueue
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::timer_wheel::TimerWheel;
+/// use rs_algorithm_practise::supplementary::clock::FakeClock;
+/// use std::time::Duration;
+///
+/// let clock = FakeClock::new();
+/// let mut timeouts = TimerWheel::with_clock(Duration::from_millis(10), clock.clone());
+/// let keys: Vec<_> = (0..10_000)
+///     .map(|request| timeouts.insert(request, Duration::from_millis(500 + request)))
+///     .collect();
+/// // most requests complete in time
+/// for key in &keys[10..] {
+///     timeouts.remove(*key);
+/// }
+///
+/// clock.advance(Duration::from_millis(505));
+/// assert_eq!(timeouts.poll_expired(), Some(0));
+/// // the other deadlines are rounded up to the next tick, at 510ms
+/// assert_eq!(timeouts.poll_expired(), None);
+/// clock.advance(Duration::from_millis(5));
+/// let mut expired: Vec<u64> = std::iter::from_fn(|| timeouts.poll_expired()).collect();
+/// expired.sort();
+/// assert_eq!(expired, (1..10).collect::<Vec<_>>());
+/// assert!(timeouts.is_empty());
+/// ```
+pub struct TimerWheel<T, C = SystemClock> {
+    entries: Slab<Entry<T>>,
+    levels: Vec<Level>,
+    // the timers more than 64^LEVELS ticks ahead
+    overflow: Vec<Key>,
+    // the timers that have expired but were not polled yet
+    ready: RingQueue<Key>,
+    start: Instant,
+    tick: Duration,
+    // the number of ticks the wheel has advanced since `start`
+    elapsed: u64,
+    clock: C,
+}
+
+impl<T> TimerWheel<T> {
+    /// create an empty wheel with ticks of `tick` on the system clock
+    ///
+    /// # Panics
+    /// if `tick` is zero
+    #[inline]
+    pub fn new(tick: Duration) -> Self {
+        Self::with_clock(tick, SystemClock)
+    }
+}
+
+impl<T, C: Clock> TimerWheel<T, C> {
+    /// create an empty wheel with ticks of `tick`, that reads the time from `clock`
+    ///
+    /// # Panics
+    /// if `tick` is zero
+    pub fn with_clock(tick: Duration, clock: C) -> Self {
+        assert!(
+            !tick.is_zero(),
+            "the tick of a timer wheel must not be zero"
+        );
+        TimerWheel {
+            entries: Slab::new(),
+            levels: (0..LEVELS)
+                .map(|_| Level {
+                    slots: (0..SLOTS).map(|_| Vec::new()).collect(),
+                    occupied: 0,
+                })
+                .collect(),
+            overflow: Vec::new(),
+            ready: RingQueue::new(),
+            start: clock.now(),
+            tick,
+            elapsed: 0,
+            clock,
+        }
+    }
+
+    /// insert a timer that expires after `delay`, and return the key to cancel it.
+    /// a delay too large for an `Instant`, e.g. `Duration::MAX`, never expires
+    #[inline]
+    pub fn insert(&mut self, item: T, delay: Duration) -> Key {
+        match self.clock.now().checked_add(delay) {
+            Some(deadline) => self.insert_at(item, deadline),
+            None => self.insert_when(item, u64::MAX),
+        }
+    }
+
+    /// insert a timer that expires at `deadline`, and return the key to cancel it
+    pub fn insert_at(&mut self, item: T, deadline: Instant) -> Key {
+        let since_start = deadline.saturating_duration_since(self.start);
+        let when = since_start.as_nanos().div_ceil(self.tick.as_nanos());
+        self.insert_when(item, u64::try_from(when).unwrap_or(u64::MAX))
+    }
+
+    // insert a timer that expires at the tick `when`
+    fn insert_when(&mut self, item: T, when: u64) -> Key {
+        let key = self.entries.insert(Entry { item, when });
+        self.schedule(key, when);
+        key
+    }
+
+    /// cancel the timer of `key` and return its item, or `None` if it already
+    /// expired or was cancelled
+    #[inline]
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        // the key is left in its slot, and skipped when the slot is reached
+        self.entries.remove(key).map(|entry| entry.item)
+    }
+
+    /// whether the timer of `key` has not been polled or cancelled yet
+    #[inline]
+    pub fn contains(&self, key: Key) -> bool {
+        self.entries.contains(key)
+    }
+
+    /// advance the wheel to the current time, and remove and return an expired
+    /// item if there is one. the items of the same tick expire in no particular order
+    pub fn poll_expired(&mut self) -> Option<T> {
+        self.advance();
+        while let Some(key) = self.ready.pop_front() {
+            if let Some(entry) = self.entries.remove(key) {
+                return Some(entry.item);
+            }
+        }
+        None
+    }
+
+    /// block until a timer expires and return its item, or return `None` at once
+    /// if the wheel is empty
+    pub fn wait(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.poll_expired() {
+                return Some(item);
+            }
+            if self.entries.is_empty() {
+                return None;
+            }
+            // the next slot to reach, its timers expire or move to a lower level
+            let (tick, _) = self
+                .next_expiration()
+                .expect("the timers that did not expire are in a slot");
+            self.clock.sleep_until(self.instant(tick));
+        }
+    }
+
+    /// the number of timers that have not been polled or cancelled
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    // the instant a tick starts at
+    fn instant(&self, tick: u64) -> Instant {
+        let nanos = self.tick.as_nanos() * tick as u128;
+        let secs = (nanos / 1_000_000_000) as u64;
+        self.start + Duration::new(secs, (nanos % 1_000_000_000) as u32)
+    }
+
+    // put a timer in the ready list, in a slot, or in the overflow list
+    fn schedule(&mut self, key: Key, when: u64) {
+        if when <= self.elapsed {
+            self.ready.push_back(key);
+            return;
+        }
+        // the highest bit where `when` differs from the current tick picks the level
+        let significant = 63 - (self.elapsed ^ when).leading_zeros();
+        let level = (significant / SLOT_BITS) as usize;
+        if level >= LEVELS {
+            self.overflow.push(key);
+            return;
+        }
+        let slot = ((when >> (level as u32 * SLOT_BITS)) as usize) & (SLOTS - 1);
+        let level = &mut self.levels[level];
+        level.slots[slot].push(key);
+        level.occupied |= 1 << slot;
+    }
+
+    // the earliest tick a slot (or the overflow list) is reached, and which one.
+    // `LEVELS` stands for the overflow list
+    fn next_expiration(&self) -> Option<(u64, (usize, usize))> {
+        let mut next = None;
+        for (index, level) in self.levels.iter().enumerate() {
+            if level.occupied == 0 {
+                continue;
+            }
+            let shift = index as u32 * SLOT_BITS;
+            let now_slot = ((self.elapsed >> shift) as usize) & (SLOTS - 1);
+            // the first occupied slot from the current one, wrapping around
+            let distance = level
+                .occupied
+                .rotate_right(now_slot as u32)
+                .trailing_zeros() as usize;
+            let slot = (now_slot + distance) & (SLOTS - 1);
+            let level_start = self.elapsed >> (shift + SLOT_BITS) << (shift + SLOT_BITS);
+            let mut tick = level_start + ((slot as u64) << shift);
+            if slot < now_slot {
+                tick += 1 << (shift + SLOT_BITS);
+            }
+            if next.is_none_or(|(earliest, _)| tick < earliest) {
+                next = Some((tick, (index, slot)));
+            }
+        }
+        if next.is_none() && !self.overflow.is_empty() {
+            // the overflow is sorted again when the top level wraps around
+            let top = LEVELS as u32 * SLOT_BITS;
+            let tick = (self.elapsed >> top << top).saturating_add(1 << top);
+            next = Some((tick, (LEVELS, 0)));
+        }
+        next
+    }
+
+    // move the wheel to the current time, cascading the slots it reaches
+    fn advance(&mut self) {
+        let now = self.clock.now().saturating_duration_since(self.start);
+        let target = u64::try_from(now.as_nanos() / self.tick.as_nanos()).unwrap_or(u64::MAX);
+        while let Some((tick, (level, slot))) = self.next_expiration() {
+            if tick > target {
+                break;
+            }
+            self.elapsed = self.elapsed.max(tick);
+            let keys = if level == LEVELS {
+                std::mem::take(&mut self.overflow)
+            } else {
+                let level = &mut self.levels[level];
+                level.occupied &= !(1 << slot);
+                std::mem::take(&mut level.slots[slot])
+            };
+            for key in keys {
+                // cancelled timers are dropped here
+                if let Some(when) = self.entries.get(key).map(|entry| entry.when) {
+                    self.schedule(key, when);
+                }
+            }
+        }
+        self.elapsed = self.elapsed.max(target);
+    }
+}
+
+impl<T, C> Debug for TimerWheel<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimerWheel")
+            .field("len", &self.entries.len())
+            .field("tick", &self.tick)
+            .field("elapsed", &self.elapsed)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supplementary::clock::FakeClock;
+    use crate::supplementary::random::RandomGen;
+
+    #[test]
+    fn test_expire_on_time() {
+        let clock = FakeClock::new();
+        let tick = Duration::from_millis(1);
+        let mut wheel = TimerWheel::with_clock(tick, clock.clone());
+        // delays on every level, in milliseconds
+        let mut rng = RandomGen::new(7);
+        let delays: Vec<u64> = (0..2000)
+            .map(|i| match i % 4 {
+                0 => rng.rand_usize(64),
+                1 => rng.rand_usize(4096),
+                2 => rng.rand_usize(300_000),
+                _ => rng.rand_usize(20_000_000),
+            } as u64)
+            .collect();
+        let keys: Vec<Key> = delays
+            .iter()
+            .enumerate()
+            .map(|(i, &delay)| wheel.insert(i, Duration::from_millis(delay)))
+            .collect();
+        // cancel a tenth of the timers
+        for key in keys.iter().step_by(10) {
+            assert!(wheel.remove(*key).is_some());
+        }
+
+        let mut expired = vec![false; delays.len()];
+        let mut now = 0;
+        while !wheel.is_empty() {
+            // jump in uneven steps, sometimes a lot of them at once
+            let step = 1 + rng.rand_usize(if now % 3 == 0 { 50 } else { 50_000 }) as u64;
+            now += step;
+            clock.advance(Duration::from_millis(step));
+            while let Some(i) = wheel.poll_expired() {
+                assert!(!expired[i]);
+                expired[i] = true;
+                assert!(delays[i] <= now, "timer {} expired early", i);
+            }
+            // everything due has expired
+            for (i, &delay) in delays.iter().enumerate() {
+                if i % 10 != 0 && delay <= now {
+                    assert!(expired[i], "timer {} did not expire at {}", i, now);
+                }
+            }
+        }
+        // exactly the timers that were not cancelled expired
+        assert!(expired.iter().enumerate().all(|(i, &e)| e == (i % 10 != 0)));
+    }
+
+    #[test]
+    fn test_wait_and_overflow() {
+        let clock = FakeClock::new();
+        let start = clock.now();
+        let mut wheel = TimerWheel::with_clock(Duration::from_nanos(1), clock.clone());
+        // beyond 64^6 ticks
+        let far = Duration::from_secs(200);
+        wheel.insert("far", far);
+        wheel.insert("near", Duration::from_nanos(100));
+        let soon = wheel.insert("soon", Duration::from_nanos(150));
+        assert_eq!(wheel.remove(soon), Some("soon"));
+        assert!(!wheel.contains(soon));
+
+        assert_eq!(wheel.wait(), Some("near"));
+        assert_eq!(clock.now() - start, Duration::from_nanos(100));
+        assert_eq!(wheel.wait(), Some("far"));
+        assert_eq!(clock.now() - start, far);
+        assert_eq!(wheel.wait(), None);
+    }
+
+    #[test]
+    fn test_huge_delay() {
+        let clock = FakeClock::new();
+        let mut wheel = TimerWheel::with_clock(Duration::from_millis(1), clock.clone());
+        let never = wheel.insert("never", Duration::MAX);
+        wheel.insert("near", Duration::from_millis(1));
+        assert_eq!(wheel.wait(), Some("near"));
+
+        clock.advance(Duration::from_secs(1 << 40));
+        assert_eq!(wheel.poll_expired(), None);
+        assert!(wheel.contains(never));
+        assert_eq!(wheel.remove(never), Some("never"));
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_rounds_deadlines_up() {
+        let clock = FakeClock::new();
+        let mut wheel = TimerWheel::with_clock(Duration::from_millis(10), clock.clone());
+        wheel.insert(1, Duration::from_millis(15));
+        wheel.insert(0, Duration::ZERO);
+        assert_eq!(wheel.poll_expired(), Some(0));
+        clock.advance(Duration::from_millis(15));
+        // the deadline is in the middle of the second tick
+        assert_eq!(wheel.poll_expired(), None);
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(wheel.poll_expired(), Some(1));
+    }
+}
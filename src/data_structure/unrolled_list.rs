@@ -0,0 +1,684 @@
+use super::linked_list::IndexOutOfBounds;
+use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::{ptr, slice};
+
+// a node of the list, with up to `N` elements in the front of `items`
+struct Node<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+    next: Option<Box<Node<T, N>>>,
+}
+
+impl<T, const N: usize> Node<T, N> {
+    fn new() -> Box<Self> {
+        Box::new(Node {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+            next: None,
+        })
+    }
+
+    fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are initialized
+        unsafe { slice::from_raw_parts(self.items.as_ptr().cast(), self.len) }
+    }
+
+    // the elements and the link to the next node, borrowed at the same time
+    fn parts_mut(&mut self) -> (&mut [T], &mut Option<Box<Node<T, N>>>) {
+        // SAFETY: the first `len` items are initialized
+        let items = unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr().cast(), self.len) };
+        (items, &mut self.next)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.parts_mut().0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // insert an element at `index`, the node must not be full
+    fn insert(&mut self, index: usize, data: T) {
+        assert!(index <= self.len && !self.is_full());
+        // SAFETY: the items from `index` are shifted right by one within the
+        // array, and `index` is then overwritten
+        unsafe {
+            let p = self.items.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            p.write(MaybeUninit::new(data));
+        }
+        self.len += 1;
+    }
+
+    // remove the element at `index`, which must be less than the length
+    fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len);
+        // SAFETY: the item at `index` is initialized, it is read out, and the
+        // items after it are shifted left by one over it
+        unsafe {
+            let p = self.items.as_mut_ptr().add(index);
+            let data = p.read().assume_init();
+            ptr::copy(p.add(1), p, self.len - index - 1);
+            self.len -= 1;
+            data
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the item at the old `len - 1` is initialized, and no longer
+        // counted, so it is not dropped again
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    // move the upper half of the elements to a new node, linked after this one
+    fn split(&mut self) {
+        let mid = self.len / 2;
+        let mut node = Node::new();
+        // SAFETY: the items `mid..len` are initialized, and are moved to the
+        // front of the empty node
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.items.as_ptr().add(mid),
+                node.items.as_mut_ptr(),
+                self.len - mid,
+            );
+        }
+        node.len = self.len - mid;
+        self.len = mid;
+        node.next = self.next.take();
+        self.next = Some(node);
+    }
+
+    // move all the elements of the next node into this one and unlink it,
+    // they must fit
+    fn merge_next(&mut self) {
+        let Some(mut next) = self.next.take() else {
+            return;
+        };
+        assert!(self.len + next.len <= N);
+        // SAFETY: the items of `next` are initialized, and are moved after the
+        // items of this node, where there is room for them
+        unsafe {
+            ptr::copy_nonoverlapping(
+                next.items.as_ptr(),
+                self.items.as_mut_ptr().add(self.len),
+                next.len,
+            );
+        }
+        self.len += next.len;
+        next.len = 0;
+        self.next = next.next.take();
+    }
+}
+
+impl<T, const N: usize> Drop for Node<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: the elements are dropped once, as the node is going away.
+        // the next node is dropped by the list, see `UnrolledList::drop`
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+/// a linked list whose nodes hold up to `N` elements each, in an array.
+///
+/// an unrolled list sits between a [`LinkedList`] and a `Vec`: walking it
+/// visits one node per `N` elements, and reads each node's elements from one
+/// contiguous array, so it is much more cache-friendly than a linked list.
+/// inserting or removing an element in the middle finds the node in
+/// `O(n / N)`, and shifts at most `N` elements within it, instead of the
+/// `O(n)` shift of a `Vec`. so with `N` around `√n`, both take `O(√n)`.
+///
+/// every node except the last one is kept at least half full: a full node
+/// is split in two when an element is inserted, and a node that drops below
+/// half is merged with the next node, or takes its first element.
+///
+/// [`LinkedList`]: super::linked_list::LinkedList
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::data_structure::unrolled_list::UnrolledList;
+///
+/// let mut list: UnrolledList<char, 4> = "hello world".chars().collect();
+/// list.insert(',', 5).unwrap();
+/// assert_eq!(list.remove(6), Ok(' '));
+/// assert_eq!(list.get(6), Some(&'w'));
+/// assert_eq!(list.iter().collect::<String>(), "hello,world");
+/// ```
+pub struct UnrolledList<T, const N: usize> {
+    head: Option<Box<Node<T, N>>>,
+    len: usize,
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    /// create an empty list. `N` must be at least 2, so a full node can be split
+    #[inline]
+    pub fn new() -> Self {
+        const {
+            assert!(
+                N >= 2,
+                "an UnrolledList node needs room for at least 2 elements"
+            )
+        };
+        UnrolledList { head: None, len: 0 }
+    }
+
+    /// the number of elements, in O(1)
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the element at a given index, or `None` if the index is out of bound
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let mut node = self.head.as_deref();
+        while let Some(current) = node {
+            if index < current.len {
+                return current.as_slice().get(index);
+            }
+            index -= current.len;
+            node = current.next.as_deref();
+        }
+        None
+    }
+
+    /// the mutable element at a given index, or `None` if the index is out of bound
+    pub fn get_mut(&mut self, mut index: usize) -> Option<&mut T> {
+        let mut node = self.head.as_deref_mut();
+        while let Some(current) = node {
+            if index < current.len {
+                return current.as_mut_slice().get_mut(index);
+            }
+            index -= current.len;
+            node = current.next.as_deref_mut();
+        }
+        None
+    }
+
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// the last element, this function needs to walk over the nodes
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// insert an element at a given index, splitting the node there if it is full
+    ///
+    /// # Arguments
+    /// `data: T`: the element to be inserted.
+    ///
+    /// `index: usize`: insert the element at this index, at most the length of the list.
+    ///
+    /// # Returns
+    /// `Err(IndexOutOfBounds)` if the index is greater than the length of the list,
+    /// the list is not changed.
+    pub fn insert(&mut self, data: T, index: usize) -> Result<(), IndexOutOfBounds> {
+        if index > self.len {
+            return Err(IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        // an index at the end of a node inserts into that node,
+        // so appending fills the last node
+        let (node, mut offset) = match index {
+            0 => (0, 0),
+            _ => {
+                let (node, offset) = self.locate(index - 1);
+                (node, offset + 1)
+            }
+        };
+        let link = self.link_mut(node);
+        let mut current = link.get_or_insert_with(Node::new);
+        if current.is_full() {
+            current.split();
+            if offset > current.len {
+                offset -= current.len;
+                current = current.next.as_mut().expect("a split node has a next node");
+            }
+        }
+        current.insert(offset, data);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// delete the element at a given index and return it, merging the node
+    /// there with the next one if it drops below half full
+    ///
+    /// # Returns
+    /// `Err(IndexOutOfBounds)` if the index is not less than the length of the list,
+    /// the list is not changed.
+    pub fn remove(&mut self, index: usize) -> Result<T, IndexOutOfBounds> {
+        if index >= self.len {
+            return Err(IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        let (node, offset) = self.locate(index);
+        let link = self.link_mut(node);
+        let current = link.as_mut().expect("`locate` returns an existing node");
+        let data = current.remove(offset);
+        if current.len < N / 2 {
+            if let Some(next) = current.next.as_mut() {
+                if current.len + next.len <= N {
+                    current.merge_next();
+                } else {
+                    let first = next.remove(0);
+                    let len = current.len;
+                    current.insert(len, first);
+                }
+            }
+        }
+        if current.len == 0 {
+            // only the last node can become empty
+            *link = None;
+        }
+        self.len -= 1;
+        Ok(data)
+    }
+
+    /// add an element at the head of the list
+    #[inline]
+    pub fn push_front(&mut self, data: T) {
+        let _ = self.insert(data, 0);
+    }
+
+    /// add an element at the end of the list, this function needs to walk over the nodes
+    #[inline]
+    pub fn push_back(&mut self, data: T) {
+        let _ = self.insert(data, self.len);
+    }
+
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(0).ok()
+    }
+
+    /// remove the last element, this function needs to walk over the nodes
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.len.checked_sub(1)?).ok()
+    }
+
+    pub fn clear(&mut self) {
+        // unlink the nodes one by one, dropping the first node
+        // would drop the rest of the list recursively
+        let mut node = self.head.take();
+        while let Some(mut current) = node {
+            node = current.next.take();
+        }
+        self.len = 0;
+    }
+
+    /// iterate over the elements, node by node
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            items: [].iter(),
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut {
+            items: [].iter_mut(),
+            next: self.head.as_deref_mut(),
+            len: self.len,
+        }
+    }
+
+    // the number of the node that holds the element at `index`,
+    // and the offset of the element in it. the index must be in bound
+    fn locate(&self, mut index: usize) -> (usize, usize) {
+        let mut node = self.head.as_deref();
+        let mut number = 0;
+        while let Some(current) = node {
+            if index < current.len {
+                break;
+            }
+            index -= current.len;
+            node = current.next.as_deref();
+            number += 1;
+        }
+        (number, index)
+    }
+
+    // the link to the node with a given number, which is the empty link
+    // after the last node if the number is the number of nodes
+    fn link_mut(&mut self, number: usize) -> &mut Option<Box<Node<T, N>>> {
+        let mut link = &mut self.head;
+        for _ in 0..number {
+            link = &mut link.as_mut().expect("the node exists").next;
+        }
+        link
+    }
+}
+
+impl<T: PartialEq, const N: usize> UnrolledList<T, N> {
+    /// whether the list contains an element equal to `value`, in O(n)
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|item| item == value)
+    }
+}
+
+impl<T, const N: usize> Default for UnrolledList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for UnrolledList<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// an iterator over the elements of an [`UnrolledList`]
+pub struct Iter<'a, T, const N: usize> {
+    // the elements left in the current node
+    items: slice::Iter<'a, T>,
+    next: Option<&'a Node<T, N>>,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                self.len -= 1;
+                return Some(item);
+            }
+            let node = self.next?;
+            self.items = node.as_slice().iter();
+            self.next = node.next.as_deref();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+
+/// an iterator over mutable references to the elements of an [`UnrolledList`]
+pub struct IterMut<'a, T, const N: usize> {
+    items: slice::IterMut<'a, T>,
+    next: Option<&'a mut Node<T, N>>,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                self.len -= 1;
+                return Some(item);
+            }
+            let (items, next) = self.next.take()?.parts_mut();
+            self.items = items.iter_mut();
+            self.next = next.as_deref_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IterMut<'_, T, N> {}
+
+/// an iterator that moves the elements out of an [`UnrolledList`]
+pub struct IntoIter<T, const N: usize> {
+    list: UnrolledList<T, N>,
+    // whether the elements of the first node are reversed, to be popped
+    reversed: bool,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.list.head.as_mut()?;
+            if !self.reversed {
+                node.as_mut_slice().reverse();
+                self.reversed = true;
+            }
+            if let Some(item) = node.pop() {
+                self.list.len -= 1;
+                return Some(item);
+            }
+            self.list.head = node.next.take();
+            self.reversed = false;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> IntoIterator for UnrolledList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            list: self,
+            reversed: false,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a UnrolledList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut UnrolledList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// append the items to the back, filling the last node and then new full nodes
+impl<T, const N: usize> Extend<T> for UnrolledList<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|node| node.next.is_some()) {
+            link = &mut link.as_mut().expect("the node exists").next;
+        }
+        for item in iter {
+            if link.as_ref().is_some_and(|node| node.is_full()) {
+                link = &mut link.as_mut().expect("the node exists").next;
+            }
+            let node = link.get_or_insert_with(Node::new);
+            let len = node.len;
+            node.insert(len, item);
+            self.len += 1;
+        }
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for UnrolledList<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for UnrolledList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for UnrolledList<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for UnrolledList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supplementary::random::RandomGen;
+    use std::rc::Rc;
+
+    // check the length and that every node but the last one is at least half full
+    fn check_nodes<T, const N: usize>(list: &UnrolledList<T, N>) {
+        let mut node = list.head.as_deref();
+        let mut len = 0;
+        while let Some(current) = node {
+            assert!(current.len > 0);
+            if current.next.is_some() {
+                assert!(current.len >= N / 2, "a node is less than half full");
+            }
+            len += current.len;
+            node = current.next.as_deref();
+        }
+        assert_eq!(len, list.len());
+    }
+
+    fn check_against_vec<const N: usize>(seed: usize) {
+        let mut rng = RandomGen::new(seed);
+        let mut list: UnrolledList<usize, N> = UnrolledList::new();
+        let mut vec = Vec::new();
+        for i in 0..2000 {
+            // grow in the first half, and shrink in the second one
+            let remove = rng.rand_usize(10) < if i < 1000 { 3 } else { 7 };
+            if remove && !vec.is_empty() {
+                let index = rng.rand_usize(vec.len());
+                assert_eq!(list.remove(index), Ok(vec.remove(index)));
+            } else {
+                let index = rng.rand_usize(vec.len() + 1);
+                list.insert(i, index).unwrap();
+                vec.insert(index, i);
+            }
+            check_nodes(&list);
+        }
+        assert!(list.iter().eq(&vec));
+        for (index, x) in vec.iter().enumerate() {
+            assert_eq!(list.get(index), Some(x));
+        }
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        check_against_vec::<2>(1);
+        check_against_vec::<3>(2);
+        check_against_vec::<16>(3);
+
+        let mut list: UnrolledList<i32, 4> = UnrolledList::new();
+        assert_eq!(
+            list.insert(1, 1),
+            Err(IndexOutOfBounds { index: 1, len: 0 })
+        );
+        assert_eq!(list.remove(0), Err(IndexOutOfBounds { index: 0, len: 0 }));
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!((list.front(), list.back()), (Some(&1), Some(&3)));
+        *list.get_mut(1).unwrap() *= 10;
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(20));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty() && list.head.is_none());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut list: UnrolledList<i32, 3> = (1..=10).collect();
+        check_nodes(&list);
+        assert_eq!(list.iter().len(), 10);
+        for x in &mut list {
+            *x *= 2;
+        }
+        list.extend([0, -1]);
+        check_nodes(&list);
+        assert!(list.contains(&20) && !list.contains(&1));
+        assert_eq!(
+            format!("{:?}", list),
+            "[2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 0, -1]"
+        );
+
+        let clone = list.clone();
+        assert_eq!(clone, list);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.by_ref().take(4).collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+        assert_eq!(iter.len(), 8);
+        assert!(iter.eq(clone.iter().skip(4).copied()));
+
+        let mut list: UnrolledList<i32, 2> = UnrolledList::new();
+        assert_eq!(list.iter().next(), None);
+        assert_eq!(list.iter_mut().next(), None);
+        list.clear();
+        assert_eq!(list.into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let token = Rc::new(());
+        {
+            let mut list: UnrolledList<Rc<()>, 4> = (0..20).map(|_| token.clone()).collect();
+            for i in (0..10).rev() {
+                drop(list.remove(i * 2));
+            }
+            let mut iter = list.into_iter();
+            drop(iter.next());
+            assert_eq!(Rc::strong_count(&token), 1 + 9);
+        }
+        assert_eq!(Rc::strong_count(&token), 1);
+    }
+
+    #[test]
+    fn test_long_list() {
+        let list: UnrolledList<u32, 2> = (0..1_000_000).collect();
+        assert_eq!(list.len(), 1_000_000);
+        assert_eq!(list.get(999_999), Some(&999_999));
+        // dropped node by node, without recursion
+    }
+}
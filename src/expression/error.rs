@@ -0,0 +1,109 @@
+use super::grammar::Arity;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// the error of parsing or evaluating an expression.
+///
+/// every error has the position where it happened, as a byte offset
+/// into the input starting at 0.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    /// a character that does not start any token, e.g. `#`
+    UnknownToken { position: usize, found: String },
+    /// a malformed number, e.g. `1.2.3`
+    InvalidNumber { position: usize, found: String },
+    /// a token where it is not allowed, e.g. the `2` in `1 2`
+    UnexpectedToken {
+        position: usize,
+        found: String,
+        expected: &'static str,
+    },
+    /// the input ended in the middle of an expression, e.g. `1 +`
+    UnexpectedEnd {
+        position: usize,
+        expected: &'static str,
+    },
+    /// a `(` that is never closed
+    UnclosedParenthesis { position: usize },
+    /// a `)` without a matching `(`
+    UnmatchedParenthesis { position: usize },
+    /// a call of a function the grammar does not define
+    UnknownFunction { position: usize, name: String },
+    /// a call of a function with the wrong number of arguments
+    WrongArgumentCount {
+        position: usize,
+        name: String,
+        expected: Arity,
+        found: usize,
+    },
+    /// a variable without a value when evaluating
+    UnknownVariable { position: usize, name: String },
+}
+
+impl ExpressionError {
+    /// the byte offset into the input where the error happened
+    pub fn position(&self) -> usize {
+        match self {
+            ExpressionError::UnknownToken { position, .. }
+            | ExpressionError::InvalidNumber { position, .. }
+            | ExpressionError::UnexpectedToken { position, .. }
+            | ExpressionError::UnexpectedEnd { position, .. }
+            | ExpressionError::UnclosedParenthesis { position }
+            | ExpressionError::UnmatchedParenthesis { position }
+            | ExpressionError::UnknownFunction { position, .. }
+            | ExpressionError::WrongArgumentCount { position, .. }
+            | ExpressionError::UnknownVariable { position, .. } => *position,
+        }
+    }
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpressionError::UnknownToken { position, found } => {
+                write!(f, "unknown token '{}' at position {}", found, position)
+            }
+            ExpressionError::InvalidNumber { position, found } => {
+                write!(f, "invalid number '{}' at position {}", found, position)
+            }
+            ExpressionError::UnexpectedToken {
+                position,
+                found,
+                expected,
+            } => write!(
+                f,
+                "unexpected '{}' at position {}, expected {}",
+                found, position, expected
+            ),
+            ExpressionError::UnexpectedEnd { position, expected } => write!(
+                f,
+                "unexpected end of input at position {}, expected {}",
+                position, expected
+            ),
+            ExpressionError::UnclosedParenthesis { position } => {
+                write!(f, "unclosed '(' at position {}", position)
+            }
+            ExpressionError::UnmatchedParenthesis { position } => {
+                write!(f, "unmatched ')' at position {}", position)
+            }
+            ExpressionError::UnknownFunction { position, name } => {
+                write!(f, "unknown function '{}' at position {}", name, position)
+            }
+            ExpressionError::WrongArgumentCount {
+                position,
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "wrong number of arguments for '{}' at position {}: expected {}, found {}",
+                name, position, expected, found
+            ),
+            ExpressionError::UnknownVariable { position, name } => {
+                write!(f, "unknown variable '{}' at position {}", name, position)
+            }
+        }
+    }
+}
+
+impl Error for ExpressionError {}
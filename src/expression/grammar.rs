@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// how operators of the same precedence group, e.g. `1 - 2 - 3` is
+/// `(1 - 2) - 3` because `-` is left associative, and `2 ^ 3 ^ 2` is
+/// `2 ^ (3 ^ 2)` because `^` is right associative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// a binary infix operator
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    /// operators with a higher precedence bind tighter
+    pub precedence: u8,
+    pub associativity: Associativity,
+    pub apply: fn(f64, f64) -> f64,
+}
+
+impl Operator {
+    #[inline]
+    pub fn new(precedence: u8, associativity: Associativity, apply: fn(f64, f64) -> f64) -> Self {
+        Operator {
+            precedence,
+            associativity,
+            apply,
+        }
+    }
+}
+
+/// the number of arguments a function takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exact(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    #[inline]
+    pub fn accepts(self, args: usize) -> bool {
+        match self {
+            Arity::Exact(n) => args == n,
+            Arity::AtLeast(n) => args >= n,
+        }
+    }
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exact(n) => write!(f, "{}", n),
+            Arity::AtLeast(n) => write!(f, "at least {}", n),
+        }
+    }
+}
+
+/// a function called as `name(arg, ...)`
+#[derive(Debug, Clone, Copy)]
+pub struct Function {
+    pub arity: Arity,
+    /// called with exactly as many arguments as the arity accepts
+    pub apply: fn(&[f64]) -> f64,
+}
+
+impl Function {
+    #[inline]
+    pub fn new(arity: Arity, apply: fn(&[f64]) -> f64) -> Self {
+        Function { arity, apply }
+    }
+}
+
+/// the operators and functions an expression may use.
+///
+/// the default grammar has, from the loosest to the tightest:
+///
+/// | operators   | precedence | associativity |
+/// |-------------|------------|---------------|
+/// | `+` `-`     | 1          | left          |
+/// | `*` `/` `%` | 2          | left          |
+/// | unary `-`   | 3          |               |
+/// | `^`         | 4          | right         |
+///
+/// so `-2 ^ 2` is `-4`, and the functions `abs`, `sqrt`, `exp`, `ln`, `log`,
+/// `sin`, `cos`, `tan`, `floor`, `ceil`, `round`, `pow`, `min` and `max`.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::expression::grammar::{Arity, Associativity, Function, Grammar, Operator};
+/// use rs_algorithm_practise::expression::rpn::Expression;
+///
+/// let mut grammar = Grammar::default();
+/// // integer division, as tight as `*`
+/// grammar.insert_operator("//", Operator::new(2, Associativity::Left, |a, b| (a / b).floor()));
+/// grammar.insert_function("clamp", Function::new(Arity::Exact(3), |args| args[0].clamp(args[1], args[2])));
+///
+/// let expression = Expression::parse_with("clamp(x // 2, 0, 10)", &grammar).unwrap();
+/// assert_eq!(expression.evaluate(&[("x", 7.0)]), Ok(3.0));
+/// assert_eq!(expression.evaluate(&[("x", 42.0)]), Ok(10.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    operators: HashMap<String, Operator>,
+    functions: HashMap<String, Function>,
+    negation_precedence: u8,
+}
+
+impl Grammar {
+    /// a grammar without any operator or function, where unary minus has
+    /// the precedence `negation_precedence`
+    pub fn empty(negation_precedence: u8) -> Self {
+        Grammar {
+            operators: HashMap::new(),
+            functions: HashMap::new(),
+            negation_precedence,
+        }
+    }
+
+    /// add a binary operator, or replace the one with the same symbol.
+    ///
+    /// # Panics
+    /// if the symbol is empty, or has a character other than ASCII punctuation,
+    /// or one of `(`, `)`, `,`, `.` and `_`
+    pub fn insert_operator(&mut self, symbol: &str, operator: Operator) -> Option<Operator> {
+        assert!(
+            !symbol.is_empty() && symbol.chars().all(is_operator_char),
+            "invalid operator symbol '{}'",
+            symbol
+        );
+        self.operators.insert(symbol.to_string(), operator)
+    }
+
+    /// add a function, or replace the one with the same name
+    pub fn insert_function(&mut self, name: &str, function: Function) -> Option<Function> {
+        self.functions.insert(name.to_string(), function)
+    }
+
+    #[inline]
+    pub fn operator(&self, symbol: &str) -> Option<&Operator> {
+        self.operators.get(symbol)
+    }
+
+    #[inline]
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions.get(name)
+    }
+
+    /// the precedence of unary minus, compared to the binary operators
+    #[inline]
+    pub fn negation_precedence(&self) -> u8 {
+        self.negation_precedence
+    }
+
+    #[inline]
+    pub fn set_negation_precedence(&mut self, precedence: u8) {
+        self.negation_precedence = precedence;
+    }
+
+    /// the longest operator symbol `input` starts with.
+    /// `-` is always an operator, for unary minus.
+    pub fn match_operator(&self, input: &str) -> Option<&str> {
+        self.operators
+            .keys()
+            .map(String::as_str)
+            .filter(|symbol| input.starts_with(symbol))
+            .max_by_key(|symbol| symbol.len())
+            .or_else(|| input.starts_with('-').then_some("-"))
+    }
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        let mut grammar = Grammar::empty(3);
+        grammar.insert_operator("+", Operator::new(1, Associativity::Left, |a, b| a + b));
+        grammar.insert_operator("-", Operator::new(1, Associativity::Left, |a, b| a - b));
+        grammar.insert_operator("*", Operator::new(2, Associativity::Left, |a, b| a * b));
+        grammar.insert_operator("/", Operator::new(2, Associativity::Left, |a, b| a / b));
+        grammar.insert_operator("%", Operator::new(2, Associativity::Left, |a, b| a % b));
+        grammar.insert_operator("^", Operator::new(4, Associativity::Right, f64::powf));
+
+        let unary = |apply| Function::new(Arity::Exact(1), apply);
+        let functions = [
+            ("abs", unary(|args| args[0].abs())),
+            ("sqrt", unary(|args| args[0].sqrt())),
+            ("exp", unary(|args| args[0].exp())),
+            ("ln", unary(|args| args[0].ln())),
+            ("log", unary(|args| args[0].log10())),
+            ("sin", unary(|args| args[0].sin())),
+            ("cos", unary(|args| args[0].cos())),
+            ("tan", unary(|args| args[0].tan())),
+            ("floor", unary(|args| args[0].floor())),
+            ("ceil", unary(|args| args[0].ceil())),
+            ("round", unary(|args| args[0].round())),
+        ];
+        for (name, function) in functions {
+            grammar.insert_function(name, function);
+        }
+        grammar.insert_function(
+            "pow",
+            Function::new(Arity::Exact(2), |args| args[0].powf(args[1])),
+        );
+        grammar.insert_function(
+            "min",
+            Function::new(Arity::AtLeast(1), |args| {
+                args.iter().copied().fold(f64::INFINITY, f64::min)
+            }),
+        );
+        grammar.insert_function(
+            "max",
+            Function::new(Arity::AtLeast(1), |args| {
+                args.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+            }),
+        );
+        grammar
+    }
+}
+
+/// whether `c` can be part of an operator symbol
+#[inline]
+pub fn is_operator_char(c: char) -> bool {
+    c.is_ascii_punctuation() && !matches!(c, '(' | ')' | ',' | '.' | '_')
+}
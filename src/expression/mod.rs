@@ -0,0 +1,5 @@
+pub mod error;
+pub mod grammar;
+pub mod rpn;
+pub mod shunting_yard;
+pub mod token;
@@ -0,0 +1,274 @@
+use super::error::ExpressionError;
+use super::grammar::Grammar;
+use super::shunting_yard::to_rpn;
+use crate::data_structure::stack::Stack;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub enum RpnKind {
+    Number(f64),
+    Variable(String),
+    /// a binary operator, applied to the two values below it
+    Operator {
+        symbol: String,
+        apply: fn(f64, f64) -> f64,
+    },
+    /// unary minus
+    Negate,
+    /// a function, applied to the `args` values below it
+    Function {
+        name: String,
+        args: usize,
+        apply: fn(&[f64]) -> f64,
+    },
+}
+
+/// display numbers and names as they are, unary minus as `neg`,
+/// and functions as `name/args`, so variadic calls are not ambiguous
+impl Display for RpnKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpnKind::Number(value) => write!(f, "{}", value),
+            RpnKind::Variable(name) => write!(f, "{}", name),
+            RpnKind::Operator { symbol, .. } => write!(f, "{}", symbol),
+            RpnKind::Negate => write!(f, "neg"),
+            RpnKind::Function { name, args, .. } => write!(f, "{}/{}", name, args),
+        }
+    }
+}
+
+/// an item of an expression in reverse Polish notation, and the byte offset
+/// of the token it comes from
+#[derive(Debug, Clone)]
+pub struct RpnToken {
+    pub kind: RpnKind,
+    pub position: usize,
+}
+
+/// the values of the variables of an expression.
+///
+/// it is implemented by maps and arrays of `(name, value)` pairs, and by any
+/// closure `Fn(&str) -> Option<f64>`.
+pub trait Variables {
+    fn get(&self, name: &str) -> Option<f64>;
+}
+
+impl Variables for HashMap<String, f64> {
+    #[inline]
+    fn get(&self, name: &str) -> Option<f64> {
+        HashMap::get(self, name).copied()
+    }
+}
+
+impl Variables for HashMap<&str, f64> {
+    #[inline]
+    fn get(&self, name: &str) -> Option<f64> {
+        HashMap::get(self, name).copied()
+    }
+}
+
+impl<const N: usize> Variables for [(&str, f64); N] {
+    #[inline]
+    fn get(&self, name: &str) -> Option<f64> {
+        self.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl<F: Fn(&str) -> Option<f64>> Variables for F {
+    #[inline]
+    fn get(&self, name: &str) -> Option<f64> {
+        self(name)
+    }
+}
+
+/// a parsed arithmetic expression, e.g. a formula in a config file.
+///
+/// it is parsed once into reverse Polish notation with the shunting-yard
+/// algorithm (see [`to_rpn`]), and can then be evaluated many times with
+/// different variables: the RPN is read from left to right, pushing operands
+/// onto a stack of values, and replacing the top values with the result of
+/// each operator or function.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::expression::rpn::Expression;
+///
+/// let expression: Expression = "max(2, cores - 1) * 1.5".parse().unwrap();
+/// assert_eq!(expression.to_string(), "2 cores 1 - max/2 1.5 *");
+/// assert_eq!(expression.variables(), vec!["cores"]);
+/// assert_eq!(expression.evaluate(&[("cores", 8.0)]), Ok(10.5));
+///
+/// let error = expression.evaluate(&[("threads", 8.0)]).unwrap_err();
+/// assert_eq!(error.to_string(), "unknown variable 'cores' at position 7");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expression {
+    rpn: Vec<RpnToken>,
+}
+
+impl Expression {
+    /// parse an expression with the default [`Grammar`]
+    #[inline]
+    pub fn parse(input: &str) -> Result<Self, ExpressionError> {
+        Self::parse_with(input, &Grammar::default())
+    }
+
+    /// parse an expression with the operators and functions of `grammar`
+    #[inline]
+    pub fn parse_with(input: &str, grammar: &Grammar) -> Result<Self, ExpressionError> {
+        Ok(Expression {
+            rpn: to_rpn(input, grammar)?,
+        })
+    }
+
+    /// the expression in reverse Polish notation
+    #[inline]
+    pub fn rpn(&self) -> &[RpnToken] {
+        &self.rpn
+    }
+
+    /// the names of the variables, in the order of their first use
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for token in &self.rpn {
+            if let RpnKind::Variable(name) = &token.kind {
+                if !names.contains(&name.as_str()) {
+                    names.push(name.as_str());
+                }
+            }
+        }
+        names
+    }
+
+    /// evaluate the expression, looking the variables up in `variables`
+    pub fn evaluate(&self, variables: &impl Variables) -> Result<f64, ExpressionError> {
+        let mut values = Stack::with_capacity(self.rpn.len());
+        for token in &self.rpn {
+            let value = match &token.kind {
+                RpnKind::Number(value) => *value,
+                RpnKind::Variable(name) => {
+                    variables
+                        .get(name)
+                        .ok_or_else(|| ExpressionError::UnknownVariable {
+                            position: token.position,
+                            name: name.clone(),
+                        })?
+                }
+                RpnKind::Operator { apply, .. } => {
+                    let b = pop(&mut values);
+                    let a = pop(&mut values);
+                    apply(a, b)
+                }
+                RpnKind::Negate => -pop(&mut values),
+                RpnKind::Function { args, apply, .. } => {
+                    let mut arguments: Vec<f64> = (0..*args).map(|_| pop(&mut values)).collect();
+                    arguments.reverse();
+                    apply(&arguments)
+                }
+            };
+            values.push(value);
+        }
+        Ok(pop(&mut values))
+    }
+}
+
+// the parser only builds well formed RPN, so there are always enough values
+#[inline]
+fn pop(values: &mut Stack<f64>) -> f64 {
+    values
+        .pop()
+        .expect("a parsed expression has enough operands")
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// display the expression in reverse Polish notation, separated by spaces
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, token) in self.rpn.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", token.kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// parse and evaluate an expression without variables, with the default [`Grammar`]
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::expression::rpn::eval;
+///
+/// assert_eq!(eval("2 ^ 3 ^ 2 / (1 + 7)"), Ok(64.0));
+/// assert!(eval("(1 + 2").is_err());
+/// ```
+pub fn eval(input: &str) -> Result<f64, ExpressionError> {
+    Expression::parse(input)?.evaluate(&|_: &str| None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(eval("(-2) ^ 2"), Ok(4.0));
+        assert_eq!(eval("7 % 4 * -1"), Ok(-3.0));
+        assert_eq!(eval("min(4, 2, 8) + max(1) + pow(2, 10)"), Ok(1027.0));
+        assert_eq!(eval("sqrt(abs(-16)) / .5e1"), Ok(0.8));
+    }
+
+    #[test]
+    fn test_variables() {
+        let expression = Expression::parse("base * (1 + rate) ^ years - base").unwrap();
+        assert_eq!(expression.variables(), vec!["base", "rate", "years"]);
+
+        let mut variables = HashMap::new();
+        variables.insert("base".to_string(), 100.0);
+        variables.insert("rate".to_string(), 0.5);
+        variables.insert("years".to_string(), 2.0);
+        assert_eq!(expression.evaluate(&variables), Ok(125.0));
+
+        // a closure can look the variables up, here every variable is its length
+        let lookup = |name: &str| Some(name.len() as f64);
+        assert_eq!(expression.evaluate(&lookup), Ok(4.0 * 3125.0 - 4.0));
+
+        variables.remove("rate");
+        assert_eq!(
+            expression.evaluate(&variables),
+            Err(ExpressionError::UnknownVariable {
+                position: 12,
+                name: "rate".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "1 + (2 * 3".parse::<Expression>().unwrap_err().to_string(),
+            "unclosed '(' at position 4"
+        );
+        assert_eq!(
+            eval("2 * $").unwrap_err().to_string(),
+            "unknown token '$' at position 4"
+        );
+    }
+}
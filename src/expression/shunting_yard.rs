@@ -0,0 +1,355 @@
+use super::error::ExpressionError;
+use super::grammar::{Associativity, Function, Grammar, Operator};
+use super::rpn::{RpnKind, RpnToken};
+use super::token::{tokenize, Token, TokenKind};
+use crate::data_structure::stack::Stack;
+
+// a function call whose arguments are being parsed
+struct Call<'a> {
+    name: &'a str,
+    function: Function,
+    position: usize,
+    // the number of arguments before the current one
+    args: usize,
+}
+
+// an item on the operator stack, waiting for its operands
+enum Pending<'a> {
+    Operator {
+        symbol: &'a str,
+        operator: Operator,
+        position: usize,
+    },
+    Negate {
+        position: usize,
+    },
+    Paren {
+        position: usize,
+        // the function, if the parenthesis opens the arguments of a call
+        call: Option<Call<'a>>,
+    },
+}
+
+impl Pending<'_> {
+    fn precedence(&self, grammar: &Grammar) -> Option<u8> {
+        match self {
+            Pending::Operator { operator, .. } => Some(operator.precedence),
+            Pending::Negate { .. } => Some(grammar.negation_precedence()),
+            Pending::Paren { .. } => None,
+        }
+    }
+
+    fn into_rpn(self) -> RpnToken {
+        match self {
+            Pending::Operator {
+                symbol,
+                operator,
+                position,
+            } => RpnToken {
+                kind: RpnKind::Operator {
+                    symbol: symbol.to_string(),
+                    apply: operator.apply,
+                },
+                position,
+            },
+            Pending::Negate { position } => RpnToken {
+                kind: RpnKind::Negate,
+                position,
+            },
+            Pending::Paren { .. } => unreachable!("parentheses are never output"),
+        }
+    }
+}
+
+/// convert an infix expression into reverse Polish notation, with Dijkstra's
+/// shunting-yard algorithm.
+///
+/// operands go straight to the output, while operators wait on a stack until an
+/// operator that binds looser, or a closing parenthesis, shows that all their
+/// operands are out. the parser alternates between expecting an operand and
+/// expecting an operator, which tells unary minus from binary minus and
+/// catches tokens in the wrong place.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::expression::grammar::Grammar;
+/// use rs_algorithm_practise::expression::shunting_yard::to_rpn;
+///
+/// let rpn = to_rpn("3 + 4 * (2 - -1)", &Grammar::default()).unwrap();
+/// let rpn: Vec<String> = rpn.iter().map(|t| t.kind.to_string()).collect();
+/// assert_eq!(rpn.join(" "), "3 4 2 1 neg - * +");
+/// ```
+pub fn to_rpn(input: &str, grammar: &Grammar) -> Result<Vec<RpnToken>, ExpressionError> {
+    let tokens = tokenize(input, grammar)?;
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut pending = Stack::new();
+    let mut expect_operand = true;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let Token { kind, position } = &tokens[i];
+        let position = *position;
+        let unexpected = |expected| ExpressionError::UnexpectedToken {
+            position,
+            found: kind.to_string(),
+            expected,
+        };
+
+        if expect_operand {
+            match kind {
+                TokenKind::Number(value) => {
+                    output.push(RpnToken {
+                        kind: RpnKind::Number(*value),
+                        position,
+                    });
+                    expect_operand = false;
+                }
+                TokenKind::Identifier(name) if matches!(tokens.get(i + 1), Some(t) if t.kind == TokenKind::LeftParen) =>
+                {
+                    let function = *grammar.function(name).ok_or_else(|| {
+                        ExpressionError::UnknownFunction {
+                            position,
+                            name: name.clone(),
+                        }
+                    })?;
+                    i += 1;
+                    pending.push(Pending::Paren {
+                        position: tokens[i].position,
+                        call: Some(Call {
+                            name,
+                            function,
+                            position,
+                            args: 0,
+                        }),
+                    });
+                }
+                TokenKind::Identifier(name) => {
+                    output.push(RpnToken {
+                        kind: RpnKind::Variable(name.clone()),
+                        position,
+                    });
+                    expect_operand = false;
+                }
+                TokenKind::Operator(symbol) if symbol == "-" => {
+                    pending.push(Pending::Negate { position });
+                }
+                TokenKind::LeftParen => pending.push(Pending::Paren {
+                    position,
+                    call: None,
+                }),
+                // a call without arguments, e.g. `f()`
+                TokenKind::RightParen
+                    if i > 0
+                        && tokens[i - 1].kind == TokenKind::LeftParen
+                        && matches!(pending.peek(), Some(Pending::Paren { call: Some(_), .. })) =>
+                {
+                    if let Some(Pending::Paren {
+                        call: Some(call), ..
+                    }) = pending.pop()
+                    {
+                        output.push(finish_call(call, false)?);
+                    }
+                    expect_operand = false;
+                }
+                _ => return Err(unexpected("an operand")),
+            }
+        } else {
+            match kind {
+                TokenKind::Operator(symbol) => {
+                    let operator = *grammar
+                        .operator(symbol)
+                        .ok_or_else(|| unexpected("a binary operator"))?;
+                    while let Some(precedence) = pending.peek().and_then(|p| p.precedence(grammar))
+                    {
+                        let pops = precedence > operator.precedence
+                            || (precedence == operator.precedence
+                                && operator.associativity == Associativity::Left);
+                        if !pops {
+                            break;
+                        }
+                        output.extend(pending.pop().map(Pending::into_rpn));
+                    }
+                    pending.push(Pending::Operator {
+                        symbol,
+                        operator,
+                        position,
+                    });
+                    expect_operand = true;
+                }
+                TokenKind::RightParen => {
+                    pop_operators(&mut pending, &mut output);
+                    match pending.pop() {
+                        Some(Pending::Paren {
+                            call: Some(call), ..
+                        }) => output.push(finish_call(call, true)?),
+                        Some(Pending::Paren { call: None, .. }) => {}
+                        _ => return Err(ExpressionError::UnmatchedParenthesis { position }),
+                    }
+                }
+                TokenKind::Comma => {
+                    pop_operators(&mut pending, &mut output);
+                    match pending.peek_mut() {
+                        Some(Pending::Paren {
+                            call: Some(call), ..
+                        }) => call.args += 1,
+                        _ => return Err(unexpected("an operator, ',' is only allowed in a call")),
+                    }
+                    expect_operand = true;
+                }
+                _ => return Err(unexpected("an operator")),
+            }
+        }
+        i += 1;
+    }
+
+    if expect_operand {
+        return Err(ExpressionError::UnexpectedEnd {
+            position: input.len(),
+            expected: "an operand",
+        });
+    }
+    while let Some(p) = pending.pop() {
+        match p {
+            Pending::Paren { position, .. } => {
+                return Err(ExpressionError::UnclosedParenthesis { position })
+            }
+            operator => output.push(operator.into_rpn()),
+        }
+    }
+    Ok(output)
+}
+
+// move the operators above the innermost parenthesis to the output
+fn pop_operators(pending: &mut Stack<Pending>, output: &mut Vec<RpnToken>) {
+    while let Some(p) = pending.peek() {
+        if matches!(p, Pending::Paren { .. }) {
+            break;
+        }
+        output.extend(pending.pop().map(Pending::into_rpn));
+    }
+}
+
+// check the number of arguments of a call, `last_arg` is whether an argument
+// was just closed, i.e. the call was not `f()`
+fn finish_call(call: Call, last_arg: bool) -> Result<RpnToken, ExpressionError> {
+    let args = call.args + last_arg as usize;
+    if !call.function.arity.accepts(args) {
+        return Err(ExpressionError::WrongArgumentCount {
+            position: call.position,
+            name: call.name.to_string(),
+            expected: call.function.arity,
+            found: args,
+        });
+    }
+    Ok(RpnToken {
+        kind: RpnKind::Function {
+            name: call.name.to_string(),
+            args,
+            apply: call.function.apply,
+        },
+        position: call.position,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::grammar::Arity;
+
+    fn rpn(input: &str) -> String {
+        rpn_with(input, &Grammar::default())
+    }
+
+    fn rpn_with(input: &str, grammar: &Grammar) -> String {
+        to_rpn(input, grammar)
+            .unwrap()
+            .iter()
+            .map(|t| t.kind.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(rpn("1 + 2 * 3"), "1 2 3 * +");
+        assert_eq!(rpn("(1 + 2) * 3"), "1 2 + 3 *");
+        assert_eq!(rpn("1 - 2 - 3"), "1 2 - 3 -");
+        assert_eq!(rpn("2 ^ 3 ^ 2"), "2 3 2 ^ ^");
+        assert_eq!(rpn("-2 ^ 2"), "2 2 ^ neg");
+        assert_eq!(rpn("2 * -x"), "2 x neg *");
+        assert_eq!(rpn("--1"), "1 neg neg");
+    }
+
+    #[test]
+    fn test_configurable_grammar() {
+        // `^` is left associative and looser than unary minus here
+        let mut grammar = Grammar::default();
+        grammar.insert_operator("^", Operator::new(4, Associativity::Left, f64::powf));
+        grammar.set_negation_precedence(5);
+        assert_eq!(rpn_with("2 ^ 3 ^ 2", &grammar), "2 3 ^ 2 ^");
+        assert_eq!(rpn_with("-2 ^ 2", &grammar), "2 neg 2 ^");
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(rpn("max(1, 2 + 3, x)"), "1 2 3 + x max/3");
+        assert_eq!(rpn("sqrt(abs(-4)) * 2"), "4 neg abs/1 sqrt/1 2 *");
+
+        let mut grammar = Grammar::default();
+        grammar.insert_function("answer", Function::new(Arity::Exact(0), |_| 42.0));
+        assert_eq!(rpn_with("answer() + 1", &grammar), "answer/0 1 +");
+    }
+
+    #[test]
+    fn test_errors() {
+        let grammar = Grammar::default();
+        let error = |input| to_rpn(input, &grammar).unwrap_err();
+
+        assert_eq!(
+            error("(1 + 2"),
+            ExpressionError::UnclosedParenthesis { position: 0 }
+        );
+        assert_eq!(
+            error("1 + 2)"),
+            ExpressionError::UnmatchedParenthesis { position: 5 }
+        );
+        assert_eq!(
+            error("1 + "),
+            ExpressionError::UnexpectedEnd {
+                position: 4,
+                expected: "an operand"
+            }
+        );
+        assert_eq!(error("").position(), 0);
+        assert_eq!(
+            error("1 2"),
+            ExpressionError::UnexpectedToken {
+                position: 2,
+                found: "2".to_string(),
+                expected: "an operator"
+            }
+        );
+        assert_eq!(error("1 * * 2").position(), 4);
+        assert_eq!(error("(1, 2)").position(), 2);
+        assert_eq!(
+            error("1 + foo(2)"),
+            ExpressionError::UnknownFunction {
+                position: 4,
+                name: "foo".to_string()
+            }
+        );
+        assert_eq!(
+            error("sqrt(1, 2)"),
+            ExpressionError::WrongArgumentCount {
+                position: 0,
+                name: "sqrt".to_string(),
+                expected: Arity::Exact(1),
+                found: 2
+            }
+        );
+        assert_eq!(
+            error("max()").to_string(),
+            "wrong number of arguments for 'max' at position 0: expected at least 1, found 0"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::error::ExpressionError;
+use super::grammar::Grammar;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Number(f64),
+    /// a variable, or a function if it is followed by `(`
+    Identifier(String),
+    Operator(String),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Number(value) => write!(f, "{}", value),
+            TokenKind::Identifier(name) => write!(f, "{}", name),
+            TokenKind::Operator(symbol) => write!(f, "{}", symbol),
+            TokenKind::LeftParen => write!(f, "("),
+            TokenKind::RightParen => write!(f, ")"),
+            TokenKind::Comma => write!(f, ","),
+        }
+    }
+}
+
+/// a token and its byte offset in the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: usize,
+}
+
+/// split an infix expression into tokens, skipping whitespace.
+///
+/// numbers are decimal, with an optional fraction and exponent, e.g. `1`, `.5`
+/// or `2.5e-3`. identifiers start with a letter or `_`, and go on with letters,
+/// digits and `_`. operators are the symbols of `grammar`, the longest one wins,
+/// so `**` is not read as two `*` if the grammar has both.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::expression::grammar::Grammar;
+/// use rs_algorithm_practise::expression::token::{tokenize, TokenKind};
+///
+/// let tokens = tokenize("max(x, 2)", &Grammar::default()).unwrap();
+/// let kinds: Vec<_> = tokens.into_iter().map(|t| t.kind).collect();
+/// assert_eq!(
+///     kinds,
+///     vec![
+///         TokenKind::Identifier("max".to_string()),
+///         TokenKind::LeftParen,
+///         TokenKind::Identifier("x".to_string()),
+///         TokenKind::Comma,
+///         TokenKind::Number(2.0),
+///         TokenKind::RightParen,
+///     ]
+/// );
+/// ```
+pub fn tokenize(input: &str, grammar: &Grammar) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while let Some(c) = input[position..].chars().next() {
+        let rest = &input[position..];
+        if c.is_whitespace() {
+            position += c.len_utf8();
+            continue;
+        }
+
+        let (kind, len) = if c.is_ascii_digit()
+            || (c == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let len = number_len(rest);
+            let text = &rest[..len];
+            let value = text.parse().map_err(|_| ExpressionError::InvalidNumber {
+                position,
+                found: text.to_string(),
+            })?;
+            (TokenKind::Number(value), len)
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (TokenKind::Identifier(rest[..len].to_string()), len)
+        } else {
+            match c {
+                '(' => (TokenKind::LeftParen, 1),
+                ')' => (TokenKind::RightParen, 1),
+                ',' => (TokenKind::Comma, 1),
+                _ => match grammar.match_operator(rest) {
+                    Some(symbol) => (TokenKind::Operator(symbol.to_string()), symbol.len()),
+                    None => {
+                        return Err(ExpressionError::UnknownToken {
+                            position,
+                            found: c.to_string(),
+                        })
+                    }
+                },
+            }
+        };
+        tokens.push(Token { kind, position });
+        position += len;
+    }
+    Ok(tokens)
+}
+
+/// the length of the number at the start of `s`: digits and dots, then an
+/// exponent only if it has digits, so `2e` is the number `2` and the identifier `e`
+fn number_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut len = bytes
+        .iter()
+        .take_while(|&&b| b.is_ascii_digit() || b == b'.')
+        .count();
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut end = len + 1;
+        if matches!(bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        let digits = bytes[end..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits > 0 {
+            len = end + digits;
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::grammar::{Associativity, Operator};
+
+    fn kinds(input: &str, grammar: &Grammar) -> Vec<TokenKind> {
+        tokenize(input, grammar)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let grammar = Grammar::default();
+        let tokens = tokenize(" 1.5e3*(rate_2 - .5)", &grammar).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token {
+                    kind: TokenKind::Number(1500.0),
+                    position: 1
+                },
+                Token {
+                    kind: TokenKind::Operator("*".to_string()),
+                    position: 6
+                },
+                Token {
+                    kind: TokenKind::LeftParen,
+                    position: 7
+                },
+                Token {
+                    kind: TokenKind::Identifier("rate_2".to_string()),
+                    position: 8
+                },
+                Token {
+                    kind: TokenKind::Operator("-".to_string()),
+                    position: 15
+                },
+                Token {
+                    kind: TokenKind::Number(0.5),
+                    position: 17
+                },
+                Token {
+                    kind: TokenKind::RightParen,
+                    position: 19
+                },
+            ]
+        );
+        // an exponent needs digits
+        assert_eq!(
+            kinds("2e", &grammar),
+            vec![
+                TokenKind::Number(2.0),
+                TokenKind::Identifier("e".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_longest_operator() {
+        let mut grammar = Grammar::default();
+        grammar.insert_operator("**", Operator::new(4, Associativity::Right, f64::powf));
+        assert_eq!(
+            kinds("2**3*4", &grammar),
+            vec![
+                TokenKind::Number(2.0),
+                TokenKind::Operator("**".to_string()),
+                TokenKind::Number(3.0),
+                TokenKind::Operator("*".to_string()),
+                TokenKind::Number(4.0),
+            ]
+        );
+        // unary minus is an operator even if the grammar has no binary minus
+        assert_eq!(
+            kinds("-x", &Grammar::empty(1)),
+            vec![
+                TokenKind::Operator("-".to_string()),
+                TokenKind::Identifier("x".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        let grammar = Grammar::default();
+        assert_eq!(
+            tokenize("1 + #", &grammar),
+            Err(ExpressionError::UnknownToken {
+                position: 4,
+                found: "#".to_string()
+            })
+        );
+        assert_eq!(
+            tokenize("x * 1.2.3", &grammar),
+            Err(ExpressionError::InvalidNumber {
+                position: 4,
+                found: "1.2.3".to_string()
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub mod data_structure;
+pub mod expression;
 pub mod sorting_algorithms;
 pub mod supplementary;
@@ -1,5 +1,8 @@
 pub mod bubble_sort;
 pub mod insertion_sort;
 pub mod merge_sort;
+pub mod parallel;
 pub mod quick_sort;
 pub mod selection_sort;
+pub mod string_sort;
+pub mod suffix_array;
@@ -0,0 +1,185 @@
+use rayon::ThreadPool;
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// settings shared by the parallel sorting algorithms.
+///
+/// a parallel sort splits the array and sorts the two parts with `rayon::join`.
+/// splitting tiny parts costs more than it saves, so parts that are not longer
+/// than `sequential_cutoff`, or that are at `max_depth` nested splits or deeper,
+/// are sorted sequentially instead.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::sorting_algorithms::parallel::ParallelConfig;
+/// use rs_algorithm_practise::sorting_algorithms::quick_sort::rayon_quick_sort_with;
+/// use std::sync::Arc;
+///
+/// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+/// let config = ParallelConfig {
+///     sequential_cutoff: 16,
+///     pool: Some(Arc::new(pool)),
+///     ..ParallelConfig::default()
+/// };
+///
+/// let mut arr: Vec<i32> = (0..100).rev().collect();
+/// rayon_quick_sort_with(&mut arr, &config);
+/// assert_eq!(arr, (0..100).collect::<Vec<i32>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParallelConfig {
+    /// parts with at most this many elements are sorted sequentially
+    pub sequential_cutoff: usize,
+    /// parts at this many nested splits or deeper are sorted sequentially
+    pub max_depth: usize,
+    /// the thread pool to sort on, or the global rayon pool if `None`
+    pub pool: Option<Arc<ThreadPool>>,
+}
+
+impl ParallelConfig {
+    pub const DEFAULT_SEQUENTIAL_CUTOFF: usize = 1024;
+    pub const DEFAULT_MAX_DEPTH: usize = 16;
+
+    #[inline]
+    pub fn new() -> Self {
+        ParallelConfig {
+            sequential_cutoff: Self::DEFAULT_SEQUENTIAL_CUTOFF,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            pool: None,
+        }
+    }
+
+    /// run `f` on the configured pool
+    pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(f),
+            None => f(),
+        }
+    }
+
+    /// whether a part of `len` elements at `depth` should be split again
+    #[inline]
+    pub fn should_split(&self, len: usize, depth: usize) -> bool {
+        len > self.sequential_cutoff && depth < self.max_depth
+    }
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// how a parallel sort split its array, for diagnosing unbalanced splits.
+///
+/// displayed as an indented tree, e.g.
+/// ```text
+/// split 5000 -> 2481 + 2518
+///   sequential 2481
+///   sequential 2518
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitTree {
+    /// a part of `len` elements that was sorted on a single thread
+    Sequential { len: usize },
+    /// a part of `len` elements that was split into two parts sorted in parallel
+    Split {
+        len: usize,
+        left: Box<SplitTree>,
+        right: Box<SplitTree>,
+    },
+}
+
+impl SplitTree {
+    /// the number of elements in this part
+    pub fn len(&self) -> usize {
+        match self {
+            SplitTree::Sequential { len } | SplitTree::Split { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the number of parts that were sorted sequentially
+    pub fn tasks(&self) -> usize {
+        match self {
+            SplitTree::Sequential { .. } => 1,
+            SplitTree::Split { left, right, .. } => left.tasks() + right.tasks(),
+        }
+    }
+
+    /// the largest number of nested splits
+    pub fn depth(&self) -> usize {
+        match self {
+            SplitTree::Sequential { .. } => 0,
+            SplitTree::Split { left, right, .. } => 1 + left.depth().max(right.depth()),
+        }
+    }
+
+    /// the length of the largest part that was sorted sequentially,
+    /// which bounds how well the sort was parallelised
+    pub fn largest_task(&self) -> usize {
+        match self {
+            SplitTree::Sequential { len } => *len,
+            SplitTree::Split { left, right, .. } => left.largest_task().max(right.largest_task()),
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        write!(f, "{}", "  ".repeat(indent))?;
+        match self {
+            SplitTree::Sequential { len } => writeln!(f, "sequential {}", len),
+            SplitTree::Split { len, left, right } => {
+                writeln!(f, "split {} -> {} + {}", len, left.len(), right.len())?;
+                left.fmt_indented(f, indent + 1)?;
+                right.fmt_indented(f, indent + 1)
+            }
+        }
+    }
+}
+
+impl Display for SplitTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_split() {
+        let config = ParallelConfig {
+            sequential_cutoff: 10,
+            max_depth: 2,
+            pool: None,
+        };
+        assert!(config.should_split(11, 0));
+        assert!(!config.should_split(10, 0));
+        assert!(!config.should_split(100, 2));
+    }
+
+    #[test]
+    fn test_split_tree() {
+        let tree = SplitTree::Split {
+            len: 10,
+            left: Box::new(SplitTree::Sequential { len: 3 }),
+            right: Box::new(SplitTree::Split {
+                len: 6,
+                left: Box::new(SplitTree::Sequential { len: 2 }),
+                right: Box::new(SplitTree::Sequential { len: 3 }),
+            }),
+        };
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.tasks(), 3);
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(tree.largest_task(), 3);
+        assert_eq!(
+            tree.to_string(),
+            "split 10 -> 3 + 6\n  sequential 3\n  split 6 -> 2 + 3\n    sequential 2\n    sequential 3\n"
+        );
+    }
+}
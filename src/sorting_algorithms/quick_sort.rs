@@ -1,4 +1,4 @@
-use rayon;
+use super::parallel::{ParallelConfig, SplitTree};
 /// pivot function takes the first element in an array,
 /// and moves it to its right place.
 /// every element smaller than the first element
@@ -52,6 +52,9 @@ pub fn quick_sort<T: Ord>(array: &mut [T]) {
 }
 
 /// rayon version of quick sort, speed up by thread pool.
+/// it uses the default [`ParallelConfig`] on the global rayon pool,
+/// see [`rayon_quick_sort_with`] to tune it.
+///
 /// # Arguments:
 ///
 /// `array: &mut [T]`: array to be sorted, a mutable slice. T must
@@ -61,17 +64,77 @@ pub fn quick_sort<T: Ord>(array: &mut [T]) {
 /// automatically if all of its member variables has implemented
 /// this trait
 pub fn rayon_quick_sort<T: Ord + Send>(array: &mut [T]) {
-    if array.len() <= 1_usize {
-        return;
+    rayon_quick_sort_with(array, &ParallelConfig::default());
+}
+
+/// rayon version of quick sort with a custom [`ParallelConfig`].
+/// parts that should not be split any more are sorted by [`quick_sort`].
+///
+/// # Arguments:
+///
+/// `array: &mut [T]`: array to be sorted, a mutable slice. T must
+/// implements trait `Ord` and `Send`.
+///
+/// `config: &ParallelConfig`: the sequential cutoff, the maximum parallel depth
+/// and the thread pool to sort on
+pub fn rayon_quick_sort_with<T: Ord + Send>(array: &mut [T], config: &ParallelConfig) {
+    config.install(|| parallel_quick_sort(array, config, 0, false));
+}
+
+/// same as [`rayon_quick_sort_with`], but also returns how the array was split,
+/// to diagnose unbalanced pivots or a badly tuned config.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::sorting_algorithms::parallel::ParallelConfig;
+/// use rs_algorithm_practise::sorting_algorithms::quick_sort::rayon_quick_sort_traced;
+///
+/// let config = ParallelConfig {
+///     sequential_cutoff: 2,
+///     ..ParallelConfig::default()
+/// };
+/// let mut arr = vec![3, 1, 4, 2, 5];
+/// let tree = rayon_quick_sort_traced(&mut arr, &config);
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+/// print!("{}", tree);
+/// // split 5 -> 2 + 2
+/// //   sequential 2
+/// //   sequential 2
+/// ```
+pub fn rayon_quick_sort_traced<T: Ord + Send>(
+    array: &mut [T],
+    config: &ParallelConfig,
+) -> SplitTree {
+    config
+        .install(|| parallel_quick_sort(array, config, 0, true))
+        .expect("parallel_quick_sort() returns a tree when tracing")
+}
+
+// sort `array` at `depth` splits, and return its split tree if `trace` is set
+fn parallel_quick_sort<T: Ord + Send>(
+    array: &mut [T],
+    config: &ParallelConfig,
+    depth: usize,
+    trace: bool,
+) -> Option<SplitTree> {
+    let len = array.len();
+    if !config.should_split(len, depth) {
+        quick_sort(array);
+        return trace.then_some(SplitTree::Sequential { len });
     }
 
     let pivot_point = pivot(array);
     let (left, right) = array.split_at_mut(pivot_point);
 
-    rayon::join(
-        || rayon_quick_sort(left),
-        || rayon_quick_sort(&mut right[1..]),
+    let (left, right) = rayon::join(
+        || parallel_quick_sort(left, config, depth + 1, trace),
+        || parallel_quick_sort(&mut right[1..], config, depth + 1, trace),
     );
+    Some(SplitTree::Split {
+        len,
+        left: Box::new(left?),
+        right: Box::new(right?),
+    })
 }
 
 #[cfg(test)]
@@ -99,4 +162,49 @@ mod tests {
         rayon_quick_sort(&mut arr);
         assert_eq!(vec![1, 2, 3, 4, 5], arr);
     }
+
+    #[test]
+    fn test_rayon_quick_sort_with_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let config = ParallelConfig {
+            sequential_cutoff: 8,
+            max_depth: 4,
+            pool: Some(std::sync::Arc::new(pool)),
+        };
+        let mut r = crate::supplementary::random::RandomGen::new(1);
+        let mut arr: Vec<usize> = (0..1000).map(|_| r.rand_usize(1000)).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+
+        let tree = rayon_quick_sort_traced(&mut arr, &config);
+        assert_eq!(expected, arr);
+        assert_eq!(tree.len(), 1000);
+        assert!(tree.depth() <= 4);
+        assert!(tree.tasks() > 1);
+    }
+
+    #[test]
+    fn test_rayon_quick_sort_cutoff() {
+        let config = ParallelConfig {
+            sequential_cutoff: 5,
+            ..ParallelConfig::default()
+        };
+        let mut arr = vec![5, 4, 3, 2, 1];
+        let tree = rayon_quick_sort_traced(&mut arr, &config);
+        assert_eq!(vec![1, 2, 3, 4, 5], arr);
+        assert_eq!(tree, SplitTree::Sequential { len: 5 });
+
+        // no parallel split at all
+        let config = ParallelConfig {
+            sequential_cutoff: 0,
+            max_depth: 0,
+            pool: None,
+        };
+        let mut arr = vec![2, 3, 1];
+        assert_eq!(rayon_quick_sort_traced(&mut arr, &config).tasks(), 1);
+        assert_eq!(vec![1, 2, 3], arr);
+    }
 }
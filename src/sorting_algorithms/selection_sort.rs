@@ -7,6 +7,10 @@
 /// `array: &mut Vec<T>`, and unsorted array, where T must
 /// implement trait `PartialOrd`
 pub fn selection_sort<T: PartialOrd>(array: &mut [T]) {
+    // if the only has one element, return directly
+    if array.len() <= 1 {
+        return;
+    }
     for i in 0..array.len() - 1_usize {
         // find the smallest in the rest of the array
         let mut smallest = i;
@@ -28,5 +32,9 @@ mod tests {
         let mut arr = vec![5, 4, 3, 2, 1];
         selection_sort(&mut arr);
         assert_eq!(vec![1, 2, 3, 4, 5], arr);
+
+        let mut empty: Vec<i32> = vec![];
+        selection_sort(&mut empty);
+        assert!(empty.is_empty());
     }
 }
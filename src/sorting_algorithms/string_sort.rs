@@ -0,0 +1,119 @@
+/// returns the byte of `s` at `depth`, or `None` if `s` is shorter than that.
+/// `None` is smaller than every byte, so that a string is smaller than its extensions.
+#[inline]
+fn byte_at<S: AsRef<[u8]>>(s: &S, depth: usize) -> Option<u8> {
+    s.as_ref().get(depth).copied()
+}
+
+/// multikey quick sort, also called three-way radix quick sort.
+/// Sort strings in ascending (byte-wise lexicographic) order.
+///
+/// Instead of comparing whole strings, it partitions the array by a single byte
+/// at a time into three parts: smaller, equal and greater than the pivot byte.
+/// Only the "equal" part moves on to the next byte, so common prefixes are
+/// compared only once. The expected time complexity is O(n log n + D), where D
+/// is the total length of the distinguishing prefixes.
+///
+/// For `String`s the byte-wise order is the same as the order of `str`.
+///
+/// # Arguments:
+/// `array: &mut [S]`: array to be sorted, where S must implement `AsRef<[u8]>`,
+/// e.g. `String`, `&str` or `Vec<u8>`
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::sorting_algorithms::string_sort::multikey_quick_sort;
+///
+/// let mut words = vec!["she", "sells", "sea", "shells", "by", "the", "sea", "shore"];
+/// multikey_quick_sort(&mut words);
+/// assert_eq!(words, vec!["by", "sea", "sea", "sells", "she", "shells", "shore", "the"]);
+/// ```
+pub fn multikey_quick_sort<S: AsRef<[u8]>>(array: &mut [S]) {
+    sort_from(array, 0);
+}
+
+// sort strings that share their first `depth` bytes
+fn sort_from<S: AsRef<[u8]>>(mut array: &mut [S], mut depth: usize) {
+    // the equal part is handled by the loop instead of recursion,
+    // so long common prefixes do not make the recursion deep
+    while array.len() > 1 {
+        let pivot = median_of_three(
+            byte_at(&array[0], depth),
+            byte_at(&array[array.len() / 2], depth),
+            byte_at(&array[array.len() - 1], depth),
+        );
+
+        // array[..lt] < pivot, array[lt..i] == pivot, array[gt..] > pivot
+        let (mut lt, mut i, mut gt) = (0, 0, array.len());
+        while i < gt {
+            match byte_at(&array[i], depth).cmp(&pivot) {
+                std::cmp::Ordering::Less => {
+                    array.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    gt -= 1;
+                    array.swap(i, gt);
+                }
+                std::cmp::Ordering::Equal => i += 1,
+            }
+        }
+
+        let (smaller, rest) = array.split_at_mut(lt);
+        let (equal, greater) = rest.split_at_mut(gt - lt);
+        sort_from(smaller, depth);
+        sort_from(greater, depth);
+
+        // strings that ended at this depth are all equal
+        if pivot.is_none() {
+            return;
+        }
+        array = equal;
+        depth += 1;
+    }
+}
+
+#[inline]
+fn median_of_three(a: Option<u8>, b: Option<u8>, c: Option<u8>) -> Option<u8> {
+    a.max(b).min(a.min(b).max(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multikey_quick_sort() {
+        let mut arr = vec!["banana", "apple", "", "app", "band", "apple", "b"];
+        multikey_quick_sort(&mut arr);
+        assert_eq!(
+            vec!["", "app", "apple", "apple", "b", "banana", "band"],
+            arr
+        );
+    }
+
+    #[test]
+    fn test_multikey_quick_sort_with_string() {
+        let mut r = crate::supplementary::random::RandomGen::new(42);
+        let mut arr: Vec<String> = (0..500)
+            .map(|_| {
+                let len = r.rand_usize(6);
+                (0..len)
+                    .map(|_| (b'a' + r.rand_usize(3) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        multikey_quick_sort(&mut arr);
+        assert_eq!(expected, arr);
+    }
+
+    #[test]
+    fn test_median_of_three() {
+        assert_eq!(median_of_three(Some(1), Some(2), Some(3)), Some(2));
+        assert_eq!(median_of_three(Some(3), None, Some(1)), Some(1));
+        assert_eq!(median_of_three(None, None, Some(1)), None);
+    }
+}
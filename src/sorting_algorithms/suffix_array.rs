@@ -0,0 +1,249 @@
+use std::ops::Range;
+
+/// stable counting sort of `order` by `key[i]`, where every key is smaller than `buckets`
+fn counting_sort(order: &[usize], key: &[usize], buckets: usize) -> Vec<usize> {
+    let mut starts = vec![0_usize; buckets + 1];
+    for &i in order {
+        starts[key[i] + 1] += 1;
+    }
+    for b in 1..=buckets {
+        starts[b] += starts[b - 1];
+    }
+    let mut res = vec![0_usize; order.len()];
+    for &i in order {
+        res[starts[key[i]]] = i;
+        starts[key[i]] += 1;
+    }
+    res
+}
+
+/// build the suffix array of `text` by prefix doubling with radix sort.
+///
+/// the suffix array lists the starting positions of all suffixes of `text`
+/// in lexicographic order. in round k, the suffixes are sorted by their first
+/// 2^k bytes: the rank of the first and second half of each prefix were computed
+/// in the previous round, so sorting by the pair of ranks is a two-pass radix sort.
+/// the time complexity is O(n log n).
+///
+/// # Arguments:
+/// `text: &[u8]` the text to be indexed
+///
+/// # Returns:
+/// `Vec<usize>` the starting positions of the suffixes in sorted order
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::sorting_algorithms::suffix_array::suffix_array;
+///
+/// // suffixes: "a", "ana", "anana", "banana", "na", "nana"
+/// assert_eq!(suffix_array(b"banana"), vec![5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // round 0: sort by the first byte
+    let mut rank: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+    let mut classes = 256;
+    let mut sa = counting_sort(&(0..n).collect::<Vec<_>>(), &rank, classes);
+    let mut next_rank = vec![0_usize; n];
+
+    let mut k = 1;
+    loop {
+        // sort by the second half: suffixes without a second half come first,
+        // the others follow the order of the previous round
+        let mut by_second: Vec<usize> = (n.saturating_sub(k)..n).collect();
+        by_second.extend(sa.iter().filter(|&&i| i >= k).map(|&i| i - k));
+        // then stably by the first half
+        sa = counting_sort(&by_second, &rank, classes);
+
+        // suffixes get the same rank if both halves have the same rank
+        let pair = |i: usize| (rank[i], rank.get(i + k).copied());
+        next_rank[sa[0]] = 0;
+        for j in 1..n {
+            let step = (pair(sa[j - 1]) != pair(sa[j])) as usize;
+            next_rank[sa[j]] = next_rank[sa[j - 1]] + step;
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        classes = rank[sa[n - 1]] + 1;
+
+        // every suffix has its own rank, so they are all sorted
+        if classes == n {
+            return sa;
+        }
+        k *= 2;
+    }
+}
+
+/// build the longest common prefix (LCP) array with Kasai's algorithm in O(n).
+///
+/// `lcp[i]` is the length of the longest common prefix of the suffixes
+/// `sa[i - 1]` and `sa[i]`, and `lcp[0]` is 0.
+///
+/// # Arguments:
+/// `text: &[u8]` the indexed text
+///
+/// `sa: &[usize]` the suffix array of `text`
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::sorting_algorithms::suffix_array::{lcp_array, suffix_array};
+///
+/// let sa = suffix_array(b"banana");
+/// assert_eq!(lcp_array(b"banana", &sa), vec![0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array(text: &[u8], sa: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank = vec![0_usize; n];
+    for (i, &s) in sa.iter().enumerate() {
+        rank[s] = i;
+    }
+
+    let mut lcp = vec![0_usize; n];
+    // the lcp of suffix i+1 is at least the lcp of suffix i minus one
+    let mut h = 0_usize;
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + h < n && j + h < n && text[i + h] == text[j + h] {
+            h += 1;
+        }
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// a substring search index over a text, made of its suffix array and LCP array
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuffixArray<'a> {
+    text: &'a [u8],
+    sa: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'a> SuffixArray<'a> {
+    /// build the index of `text`, in O(n log n)
+    pub fn new(text: &'a [u8]) -> Self {
+        let sa = suffix_array(text);
+        let lcp = lcp_array(text, &sa);
+        SuffixArray { text, sa, lcp }
+    }
+
+    #[inline]
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    /// the starting positions of the suffixes in sorted order
+    #[inline]
+    pub fn suffixes(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// the LCP array, see [`lcp_array`]
+    #[inline]
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    // the suffixes starting with `pattern` form a range in the suffix array
+    fn range(&self, pattern: &[u8]) -> Range<usize> {
+        let start = self.sa.partition_point(|&i| &self.text[i..] < pattern);
+        let end =
+            start + self.sa[start..].partition_point(|&i| self.text[i..].starts_with(pattern));
+        start..end
+    }
+
+    /// find every occurrence of `pattern` in O(m log n), where m is the length of the pattern
+    ///
+    /// # Returns:
+    /// `&[usize]` the starting positions of the occurrences, in the order of the suffix array
+    ///
+    /// # Examples
+    /// ```rust
+    /// use rs_algorithm_practise::sorting_algorithms::suffix_array::SuffixArray;
+    ///
+    /// let index = SuffixArray::new(b"abracadabra");
+    /// let mut found = index.find(b"abra").to_vec();
+    /// found.sort();
+    /// assert_eq!(found, vec![0, 7]);
+    /// ```
+    pub fn find(&self, pattern: &[u8]) -> &[usize] {
+        &self.sa[self.range(pattern)]
+    }
+
+    /// count the occurrences of `pattern`
+    #[inline]
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        self.range(pattern).len()
+    }
+
+    /// the longest substring that occurs at least twice, if any
+    pub fn longest_repeated_substring(&self) -> Option<&'a [u8]> {
+        let (i, &len) = self.lcp.iter().enumerate().max_by_key(|&(_, len)| len)?;
+        match len {
+            0 => None,
+            _ => Some(&self.text[self.sa[i]..self.sa[i] + len]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sort the suffixes with the standard library, for comparison
+    fn naive_suffix_array(text: &[u8]) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..text.len()).collect();
+        sa.sort_by_key(|&i| &text[i..]);
+        sa
+    }
+
+    #[test]
+    fn test_suffix_array() {
+        assert_eq!(suffix_array(b""), Vec::<usize>::new());
+        assert_eq!(suffix_array(b"a"), vec![0]);
+        assert_eq!(suffix_array(b"aaaa"), vec![3, 2, 1, 0]);
+        for text in [
+            &b"mississippi"[..],
+            b"abracadabra",
+            b"abababab",
+            b"the quick brown fox",
+        ] {
+            assert_eq!(suffix_array(text), naive_suffix_array(text));
+        }
+
+        let mut r = crate::supplementary::random::RandomGen::new(3);
+        let text: Vec<u8> = (0..2000).map(|_| b'a' + r.rand_usize(3) as u8).collect();
+        assert_eq!(suffix_array(&text), naive_suffix_array(&text));
+    }
+
+    #[test]
+    fn test_lcp_array() {
+        let text = b"mississippi";
+        let sa = suffix_array(text);
+        assert_eq!(lcp_array(text, &sa), vec![0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]);
+        assert_eq!(lcp_array(b"", &[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_find() {
+        let index = SuffixArray::new(b"mississippi");
+        let mut found = index.find(b"ssi").to_vec();
+        found.sort();
+        assert_eq!(found, vec![2, 5]);
+        assert_eq!(index.count(b"i"), 4);
+        assert_eq!(index.count(b""), 11);
+        assert!(index.find(b"sip").len() == 1);
+        assert!(index.find(b"xyz").is_empty());
+        assert!(index.find(b"ippix").is_empty());
+        assert_eq!(index.longest_repeated_substring(), Some(&b"issi"[..]));
+        assert_eq!(SuffixArray::new(b"abc").longest_repeated_substring(), None);
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// a source of the current time, and a way to wait for a later time.
+///
+/// timers take the clock as a type parameter, so they read the system clock by
+/// default, and tests can drive them with a [`FakeClock`] instead of sleeping.
+pub trait Clock {
+    fn now(&self) -> Instant;
+
+    /// block the current thread until `now()` reaches `deadline`
+    fn sleep_until(&self, deadline: Instant);
+}
+
+/// the monotonic clock of the system, see [`Instant::now`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) {
+        let now = Instant::now();
+        if deadline > now {
+            thread::sleep(deadline - now);
+        }
+    }
+}
+
+/// a clock that only moves when it is told to.
+///
+/// clones share the same time, so a test can keep one clone and advance the time
+/// seen by a timer that owns another. sleeping moves the time to the deadline
+/// at once.
+///
+/// # Examples
+/// ```rust
+/// use rs_algorithm_practise::supplementary::clock::{Clock, FakeClock};
+/// use std::time::Duration;
+///
+/// let clock = FakeClock::new();
+/// let start = clock.now();
+/// let shared = clock.clone();
+/// shared.advance(Duration::from_secs(5));
+/// assert_eq!(clock.now() - start, Duration::from_secs(5));
+///
+/// clock.sleep_until(start + Duration::from_secs(60));
+/// assert_eq!(shared.now() - start, Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl FakeClock {
+    /// create a clock that starts at the current time of the system
+    #[inline]
+    pub fn new() -> Self {
+        FakeClock {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// move the time forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// move the time forward to `deadline`, if it is later
+    fn sleep_until(&self, deadline: Instant) {
+        let mut now = self.now.lock().unwrap_or_else(PoisonError::into_inner);
+        *now = (*now).max(deadline);
+    }
+}
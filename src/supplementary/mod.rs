@@ -1,3 +1,4 @@
+pub mod clock;
 pub mod hash;
 pub mod random;
 pub mod stepper;